@@ -1,6 +1,13 @@
 // Microsoft OAuth2 constants - updated to use the correct endpoints
 pub const MS_AUTH_URL: &str = "https://login.live.com/oauth20_authorize.srf";
 pub const MS_TOKEN_URL: &str = "https://login.live.com/oauth20_token.srf";
+// Azure application client ID, overridable through the environment
+pub const DEFAULT_CLIENT_ID: &str = "74ab16e9-5151-4478-8184-e590ba53d01d";
+pub const CLIENT_ID_ENV: &str = "MS_CLIENT_ID";
+// Set to "keyring" to keep the access token in the OS credential store
+pub const TOKEN_STORE_ENV: &str = "REDSTONIUM_TOKEN_STORE";
+pub const KEYRING_SERVICE: &str = "Redstonium";
+pub const KEYRING_USER: &str = "minecraft-access-token";
 // Use a local redirect URI
 pub const REDIRECT_URI: &str = "http://localhost:8080"; // Make sure this matches the Azure App Registration
 
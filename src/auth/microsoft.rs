@@ -4,7 +4,7 @@ use tokio::sync::oneshot;
 use tokio::task;
 use tracing::{debug, error, info, trace, warn};
 
-use super::constants::{MS_AUTH_URL, MS_TOKEN_URL, REDIRECT_URI};
+use super::constants::{CLIENT_ID_ENV, DEFAULT_CLIENT_ID, MS_AUTH_URL, MS_TOKEN_URL, REDIRECT_URI};
 
 /// Starts a local server to receive the OAuth redirect and extract the code
 fn start_local_server(tx: tokio::sync::oneshot::Sender<Result<String>>) {
@@ -122,7 +122,10 @@ async fn exchange_code_for_token(
 
 /// Get a Microsoft OAuth token using the authorization code flow with a local server
 pub async fn get_microsoft_token() -> Result<String> {
-    let client_id = String::from("74ab16e9-5151-4478-8184-e590ba53d01d");
+    let client_id = std::env::var(CLIENT_ID_ENV)
+        .ok()
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_CLIENT_ID.to_string());
 
     debug!("Creating OAuth client with client ID: {client_id}");
 
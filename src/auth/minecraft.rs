@@ -7,25 +7,26 @@ use super::constants::{MINECRAFT_AUTH_URL, MINECRAFT_ENTITLEMENT_URL, MINECRAFT_
 use super::models::{
     EntitlementResponse, MinecraftAuthRequest, MinecraftAuthResponse, MinecraftProfile,
 };
+use super::retry::send_with_retry;
 
-/// Get Minecraft access token using XSTS token and user hash
+/// Get Minecraft access token and its lifetime in seconds using XSTS token and user hash
 pub async fn get_minecraft_token(
     client: &Client,
     xsts_token: &str,
     user_hash: &str,
-) -> Result<String> {
+) -> Result<(String, u64)> {
     // Format the Xbox Live identity token for Minecraft
     let identity_token = format!("XBL3.0 x={user_hash};{xsts_token}");
 
     let minecraft_request = MinecraftAuthRequest { identity_token };
 
     debug!("Sending authentication request to Minecraft services: {MINECRAFT_AUTH_URL}");
-    let response = client
+    let request = client
         .post(MINECRAFT_AUTH_URL)
         .header(CONTENT_TYPE, "application/json")
         .header(ACCEPT, "application/json") // Explicitly add Accept header
-        .json(&minecraft_request)
-        .send()
+        .json(&minecraft_request);
+    let response = send_with_retry(request, "Minecraft authentication")
         .await
         .with_context(|| {
             "Failed to send request to Minecraft authentication endpoint".to_string()
@@ -60,16 +61,19 @@ pub async fn get_minecraft_token(
         minecraft_response.access_token.len()
     );
 
-    Ok(minecraft_response.access_token)
+    Ok((
+        minecraft_response.access_token,
+        minecraft_response.expires_in,
+    ))
 }
 
 /// Verify that the user owns Minecraft
 pub async fn verify_game_ownership(client: &Client, minecraft_token: &str) -> Result<()> {
     debug!("Verifying game ownership at: {MINECRAFT_ENTITLEMENT_URL}");
-    let response = client
+    let request = client
         .get(MINECRAFT_ENTITLEMENT_URL)
-        .header(AUTHORIZATION, format!("Bearer {minecraft_token}"))
-        .send()
+        .header(AUTHORIZATION, format!("Bearer {minecraft_token}"));
+    let response = send_with_retry(request, "Minecraft entitlements")
         .await
         .with_context(|| "Failed to send request to Minecraft entitlement endpoint".to_string())?;
 
@@ -138,10 +142,10 @@ pub async fn get_player_profile(
     minecraft_token: &str,
 ) -> Result<MinecraftProfile> {
     debug!("Retrieving Minecraft profile from: {MINECRAFT_PROFILE_URL}");
-    let response = client
+    let request = client
         .get(MINECRAFT_PROFILE_URL)
-        .header(AUTHORIZATION, format!("Bearer {minecraft_token}"))
-        .send()
+        .header(AUTHORIZATION, format!("Bearer {minecraft_token}"));
+    let response = send_with_retry(request, "Minecraft profile")
         .await
         .with_context(|| "Failed to send request to Minecraft profile endpoint".to_string())?;
 
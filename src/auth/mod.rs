@@ -7,24 +7,58 @@ mod constants;
 mod microsoft;
 mod minecraft;
 mod models;
+mod retry;
 pub mod storage;
 mod xbox;
 
-pub use models::AuthResult;
+pub use constants::{CLIENT_ID_ENV, TOKEN_STORE_ENV};
+pub use models::{AuthResult, MinecraftProfile};
 use storage::AuthStorage;
 
-pub async fn authenticate() -> Result<AuthResult> {
+/// Fetch the current player profile using an existing access token
+pub async fn fetch_profile(access_token: &str) -> Result<MinecraftProfile> {
+    let client = Client::new();
+    minecraft::get_player_profile(&client, access_token).await
+}
+
+/// Re-fetch the profile for a login and update the cached copy, so name changes show up
+pub async fn refresh_profile(auth: &mut AuthResult) -> Result<()> {
+    let profile = fetch_profile(&auth.access_token)
+        .await
+        .context("Failed to refresh player profile")?;
+    if profile.name != auth.profile.name {
+        info!(
+            "Player name changed from {} to {}",
+            auth.profile.name, profile.name
+        );
+    }
+    auth.profile = profile;
+
+    let auth_storage = AuthStorage::new().context("Failed to initialize auth storage")?;
+    if let Err(e) = auth_storage.save_auth(auth).await {
+        warn!("Failed to cache authentication: {e}");
+    }
+    Ok(())
+}
+
+/// Sign in, reusing the cached login unless it expires within `refresh_before`
+///
+/// Refreshing a login that is about to expire avoids it lapsing mid-session.
+pub async fn authenticate(refresh_before: time::Duration) -> Result<AuthResult> {
     // Initialize auth storage
     let auth_storage =
         AuthStorage::new().with_context(|| "Failed to initialize auth storage".to_string())?;
 
     // Try to load cached authentication first
     if let Some(cached_auth) = auth_storage.load_auth().await? {
-        info!(
-            "Using cached authentication for {}",
-            cached_auth.profile.name
-        );
-        return Ok(cached_auth);
+        if !cached_auth.expires_within(refresh_before) {
+            info!(
+                "Using cached authentication for {}",
+                cached_auth.profile.name
+            );
+            return Ok(cached_auth);
+        }
+        info!("Cached authentication expires soon, refreshing it now");
     }
 
     info!("No valid cached authentication found, starting fresh authentication");
@@ -62,9 +96,10 @@ pub async fn authenticate() -> Result<AuthResult> {
 
     // Step 4: Authenticate with Minecraft using XSTS token
     info!("Starting Minecraft authentication");
-    let minecraft_token = minecraft::get_minecraft_token(&client, &xsts_token, &user_hash)
-        .await
-        .with_context(|| "Failed to get Minecraft token".to_string())?;
+    let (minecraft_token, expires_in) =
+        minecraft::get_minecraft_token(&client, &xsts_token, &user_hash)
+            .await
+            .with_context(|| "Failed to get Minecraft token".to_string())?;
     info!("✓ Minecraft authentication successful");
     trace!("Minecraft token length: {}", minecraft_token.len());
 
@@ -85,6 +120,8 @@ pub async fn authenticate() -> Result<AuthResult> {
     let auth_result = AuthResult {
         access_token: minecraft_token.clone(),
         profile,
+        expires_at: time::OffsetDateTime::now_utc()
+            + time::Duration::seconds(i64::try_from(expires_in).unwrap_or(i64::MAX)),
     };
 
     // Cache the authentication result for future use
@@ -1,10 +1,20 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// The result of a successful authentication process
 #[derive(Debug)]
 pub struct AuthResult {
     pub access_token: String,
     pub profile: MinecraftProfile,
+    /// When the access token stops being accepted
+    pub expires_at: time::OffsetDateTime,
+}
+
+impl AuthResult {
+    /// Whether the token expires within the given window (or already has)
+    pub fn expires_within(&self, window: time::Duration) -> bool {
+        time::OffsetDateTime::now_utc() + window >= self.expires_at
+    }
 }
 
 /// Represents a Minecraft player profile
@@ -14,6 +24,18 @@ pub struct MinecraftProfile {
     pub name: String,
     pub skins: Option<Vec<Skin>>,
     pub capes: Option<Vec<Cape>>,
+    /// Pending account actions (e.g. a forced name change) keyed by action name
+    #[serde(rename = "profileActions", default)]
+    pub profile_actions: HashMap<String, serde_json::Value>,
+}
+
+impl MinecraftProfile {
+    /// Names of pending profile actions, which can block multiplayer
+    pub fn pending_actions(&self) -> Vec<&str> {
+        let mut actions: Vec<&str> = self.profile_actions.keys().map(String::as_str).collect();
+        actions.sort_unstable();
+        actions
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -0,0 +1,79 @@
+use crate::error::Result;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+use tracing::warn;
+
+/// Total attempts for a request before giving up on transient failures
+const MAX_ATTEMPTS: u32 = 4;
+/// Delay before the first retry when the server doesn't say how long to wait
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Longest we are willing to wait between attempts, even if asked to wait longer
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Send an auth request, retrying `429 Too Many Requests` and `5xx` responses
+///
+/// `Retry-After` is honoured when present; otherwise the delay doubles each attempt.
+/// Other responses, including permanent `4xx` errors, are returned as-is for the
+/// caller to handle.
+pub async fn send_with_retry(request: RequestBuilder, service: &str) -> Result<Response> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        let Some(current) = request.try_clone() else {
+            // Streaming bodies can't be replayed, so send once
+            return Ok(request.send().await?);
+        };
+
+        let response = current.send().await?;
+        let status = response.status();
+        if !is_transient(status) || attempt == MAX_ATTEMPTS {
+            return Ok(response);
+        }
+
+        let delay = parse_retry_after(&response)
+            .unwrap_or(backoff)
+            .min(MAX_BACKOFF);
+        warn!(
+            "⚠ {service} returned {status}, retrying in {}s (attempt {attempt}/{MAX_ATTEMPTS})",
+            delay.as_secs()
+        );
+        tokio::time::sleep(delay).await;
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+/// Whether a status is worth retrying: rate limiting or a server-side outage
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// The delay requested by a `Retry-After` header given in seconds
+fn parse_retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient(StatusCode::BAD_GATEWAY));
+        assert!(!is_transient(StatusCode::UNAUTHORIZED));
+        assert!(!is_transient(StatusCode::NOT_FOUND));
+        assert!(!is_transient(StatusCode::OK));
+    }
+}
@@ -1,41 +1,114 @@
 use crate::error::{AuthError, Result, ResultExt};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use time::{Duration, OffsetDateTime};
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
 use tokio::fs;
 use tracing::{debug, info, warn};
 
 use super::AuthResult;
+use super::constants::{KEYRING_SERVICE, KEYRING_USER, TOKEN_STORE_ENV};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CachedAuth {
-    pub access_token: String,
+    /// Absent when the token is kept in the OS keyring
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
     pub profile: super::models::MinecraftProfile,
     pub expires_at: OffsetDateTime,
 }
 
+/// Directory name used for the launcher's cache
+const APP_DIR_NAME: &str = "Redstonium";
+/// Names the cache directory had before the project was renamed
+const LEGACY_APP_DIR_NAMES: [&str; 2] = ["rustified", "Rustified"];
+
 pub struct AuthStorage {
     cache_file_path: PathBuf,
+    use_keyring: bool,
 }
 
 impl AuthStorage {
     pub fn new() -> Result<Self> {
-        let cache_dir = Self::get_cache_dir()?;
+        let cache_dir = Self::get_cache_dir(APP_DIR_NAME)?;
+        Self::migrate_legacy_cache_dir(&cache_dir);
         std::fs::create_dir_all(&cache_dir)
             .with_context(|| "Failed to create cache directory".to_string())?;
+        restrict_permissions(&cache_dir, 0o700)
+            .with_context(|| "Failed to restrict cache directory permissions".to_string())?;
 
         let cache_file_path = cache_dir.join("auth_cache.json");
+        let use_keyring = std::env::var(TOKEN_STORE_ENV)
+            .is_ok_and(|store| store.trim().eq_ignore_ascii_case("keyring"));
+
+        Ok(Self {
+            cache_file_path,
+            use_keyring,
+        })
+    }
+
+    /// The keyring entry holding the access token
+    fn keyring_entry() -> keyring::Result<keyring::Entry> {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+    }
+
+    /// Store the token in the OS keyring, returning `false` if no keyring is available
+    fn save_token_to_keyring(access_token: &str) -> bool {
+        match Self::keyring_entry().and_then(|entry| entry.set_password(access_token)) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("⚠ OS keyring unavailable ({e}); storing the access token in the cache file");
+                false
+            }
+        }
+    }
 
-        Ok(Self { cache_file_path })
+    /// Path of the auth cache file
+    pub fn cache_file_path(&self) -> &Path {
+        &self.cache_file_path
+    }
+
+    /// Move a cache directory left behind under the project's old name
+    ///
+    /// Only happens when the canonical directory doesn't exist yet, so nothing is
+    /// ever overwritten. Failure just means signing in again.
+    fn migrate_legacy_cache_dir(cache_dir: &Path) {
+        if cache_dir.exists() {
+            return;
+        }
+
+        for legacy_name in LEGACY_APP_DIR_NAMES {
+            let Ok(legacy_dir) = Self::get_cache_dir(legacy_name) else {
+                return;
+            };
+            if !legacy_dir.is_dir() {
+                continue;
+            }
+
+            if let Some(parent) = cache_dir.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            match std::fs::rename(&legacy_dir, cache_dir) {
+                Ok(()) => info!(
+                    "✓ Moved legacy cache from {} to {}",
+                    legacy_dir.display(),
+                    cache_dir.display()
+                ),
+                Err(e) => warn!(
+                    "⚠ Could not move legacy cache from {}: {e}",
+                    legacy_dir.display()
+                ),
+            }
+            return;
+        }
     }
 
-    /// Get platform-specific cache directory
-    fn get_cache_dir() -> Result<PathBuf> {
+    /// Get platform-specific cache directory for the given application name
+    fn get_cache_dir(app_name: &str) -> Result<PathBuf> {
         match std::env::consts::OS {
             "windows" => {
                 let appdata = std::env::var("APPDATA")
                     .with_context(|| "APPDATA environment variable not found".to_string())?;
-                Ok(PathBuf::from(appdata).join("Redstonium").join("cache"))
+                Ok(PathBuf::from(appdata).join(app_name).join("cache"))
             }
             "macos" => {
                 let home = std::env::var("HOME")
@@ -43,7 +116,7 @@ impl AuthStorage {
                 Ok(PathBuf::from(home)
                     .join("Library")
                     .join("Caches")
-                    .join("Redstonium"))
+                    .join(app_name))
             }
             "linux" => {
                 // Use XDG_CACHE_HOME if available, otherwise ~/.cache
@@ -54,7 +127,7 @@ impl AuthStorage {
                     },
                     PathBuf::from,
                 );
-                Ok(cache_dir.join("Redstonium"))
+                Ok(cache_dir.join(app_name))
             }
             _ => Err(AuthError::cache_error(format!(
                 "Unsupported operating system: {}",
@@ -68,11 +141,11 @@ impl AuthStorage {
     pub async fn save_auth(&self, auth: &AuthResult) -> Result<()> {
         debug!("Saving authentication to cache");
 
-        // Tokens typically expire in 24 hours, but we'll cache for 23 hours to be safe
-        let expires_at = OffsetDateTime::now_utc() + Duration::hours(23);
+        let expires_at = auth.expires_at;
+        let in_keyring = self.use_keyring && Self::save_token_to_keyring(&auth.access_token);
 
         let cached_auth = CachedAuth {
-            access_token: auth.access_token.clone(),
+            access_token: (!in_keyring).then(|| auth.access_token.clone()),
             profile: auth.profile.clone(),
             expires_at,
         };
@@ -80,7 +153,7 @@ impl AuthStorage {
         let json = serde_json::to_string_pretty(&cached_auth)
             .with_context(|| "Failed to serialize cached auth".to_string())?;
 
-        fs::write(&self.cache_file_path, json)
+        write_private_file(&self.cache_file_path, json.as_bytes())
             .await
             .with_context(|| "Failed to write auth cache file".to_string())?;
 
@@ -107,6 +180,17 @@ impl AuthStorage {
         let cached_auth: CachedAuth = serde_json::from_str(&content)
             .with_context(|| "Failed to parse cached auth".to_string())?;
 
+        let access_token = match cached_auth.access_token {
+            Some(token) => token,
+            None => match Self::keyring_entry().and_then(|entry| entry.get_password()) {
+                Ok(token) => token,
+                Err(e) => {
+                    warn!("Cached access token not found in the OS keyring: {e}");
+                    return Ok(None);
+                }
+            },
+        };
+
         // Check if token is still valid
         let now = OffsetDateTime::now_utc();
         if now >= cached_auth.expires_at {
@@ -129,13 +213,19 @@ impl AuthStorage {
         );
 
         Ok(Some(AuthResult {
-            access_token: cached_auth.access_token,
+            access_token,
             profile: cached_auth.profile,
+            expires_at: cached_auth.expires_at,
         }))
     }
 
     /// Clear cached authentication
     pub async fn clear_cache(&self) -> Result<()> {
+        // The token may be in the keyring even if keyring storage is now turned off
+        if let Ok(entry) = Self::keyring_entry() {
+            let _ = entry.delete_password();
+        }
+
         if self.cache_file_path.exists() {
             fs::remove_file(&self.cache_file_path)
                 .await
@@ -145,3 +235,55 @@ impl AuthStorage {
         Ok(())
     }
 }
+
+/// Write a file that only the current user can read (mode 0600 on Unix)
+///
+/// On Windows the cache lives under the user's profile, whose ACL already
+/// restricts access to that user.
+async fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(path).await?;
+    file.write_all(contents).await?;
+    file.flush().await?;
+
+    // `mode` only applies to new files; tighten caches written by older versions
+    restrict_permissions(path, 0o600)
+}
+
+/// Set Unix permission bits on a path; a no-op on other platforms
+#[cfg_attr(not(unix), allow(clippy::unnecessary_wraps, unused_variables))]
+fn restrict_permissions(path: &Path, mode: u32) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[tokio::test]
+    async fn test_write_private_file_is_owner_only() {
+        let path =
+            std::env::temp_dir().join(format!("redstonium-auth-test-{}.json", std::process::id()));
+        std::fs::write(&path, b"old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, b"{}").await.unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read(&path).unwrap(), b"{}");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -7,6 +7,7 @@ use super::constants::{XBL_AUTH_URL, XSTS_AUTH_URL};
 use super::models::{
     XboxLiveProperties, XboxLiveRequest, XboxLiveResponse, XstsProperties, XstsRequest,
 };
+use super::retry::send_with_retry;
 
 /// Get Xbox Live token using the Microsoft access token
 pub async fn get_xbox_live_token(client: &Client, ms_token: &str) -> Result<(String, String)> {
@@ -22,12 +23,12 @@ pub async fn get_xbox_live_token(client: &Client, ms_token: &str) -> Result<(Str
     };
 
     debug!("Sending authentication request to Xbox Live: {XBL_AUTH_URL}");
-    let response = client
+    let request = client
         .post(XBL_AUTH_URL)
         .header(CONTENT_TYPE, "application/json")
         .header(ACCEPT, "application/json")
-        .json(&xbl_request)
-        .send()
+        .json(&xbl_request);
+    let response = send_with_retry(request, "Xbox Live")
         .await
         .with_context(|| {
             "Failed to send request to Xbox Live authentication endpoint".to_string()
@@ -83,11 +84,11 @@ pub async fn get_xsts_token(client: &Client, xbl_token: &str) -> Result<String>
     };
 
     debug!("Sending XSTS authentication request to: {XSTS_AUTH_URL}");
-    let response = client
+    let request = client
         .post(XSTS_AUTH_URL)
         .header(CONTENT_TYPE, "application/json")
-        .json(&xsts_request)
-        .send()
+        .json(&xsts_request);
+    let response = send_with_retry(request, "XSTS")
         .await
         .with_context(|| "Failed to send request to XSTS authentication endpoint".to_string())?;
 
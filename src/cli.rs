@@ -1,12 +1,27 @@
 use std::env;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "Redstonium")]
 #[command(about = "A Minecraft CLI launcher written in Rust")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 pub struct Cli {
+    /// Log output format
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    pub log_format: LogFormat,
+    /// Architecture to launch for, e.g. x86_64 to run under Rosetta on Apple Silicon
+    #[arg(long, global = true, alias = "arch", value_parser = parse_target_arch)]
+    pub target_arch: Option<&'static str>,
+    /// Ignore cached version files and Java scan results for this run (caches are kept)
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+    /// Print how long each phase (downloads, Java selection, the game itself) took
+    #[arg(long, global = true)]
+    pub timings: bool,
+    /// Measure download speed on the first assets and use the fastest concurrency
+    #[arg(long, global = true)]
+    pub auto_tune: bool,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -36,19 +51,39 @@ pub enum Commands {
         /// Sort order for versions
         #[arg(long, value_enum, default_value = "newest-first")]
         sort: SortOrder,
+        /// Only show versions released on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+        /// Only show versions released on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<chrono::NaiveDate>,
+        /// Print only summary counts instead of individual versions
+        #[arg(long)]
+        count: bool,
+        /// Hide experimental, combat-test and April Fools snapshots
+        #[arg(long)]
+        exclude_experimental: bool,
+        /// Show the Java version each version needs (estimated from the version number unless --fetch-java)
+        #[arg(long)]
+        show_java: bool,
+        /// With --show-java, read each version's declared Java requirement (slower, may download)
+        #[arg(long, requires = "show_java")]
+        fetch_java: bool,
+        /// Keep only the newest patch of each release line (e.g. 1.20.6 for 1.20.x)
+        #[arg(long)]
+        latest_per_minor: bool,
     },
     /// Launch a Minecraft instance
     Launch {
         /// Instance to launch
         instance: String,
-        /// Skip file verification (faster launch)
-        #[arg(long)]
-        skip_verification: bool,
+        #[command(flatten)]
+        options: LaunchArgs,
     },
     /// Prepare (download) a Minecraft version without launching
     Prepare {
-        /// Version to prepare
-        version: String,
+        #[command(flatten)]
+        options: PrepareArgs,
     },
     /// Authentication management
     Auth {
@@ -65,31 +100,98 @@ pub enum Commands {
         #[command(subcommand)]
         action: JavaCommands,
     },
+    /// Show versions installed on disk with their size (no network needed)
+    Versions,
+    /// Inspect a single Minecraft version
+    Version {
+        #[command(subcommand)]
+        action: VersionCommands,
+    },
+    /// Find versions by fuzzy match, best matches first (e.g. `119` finds 1.19.x)
+    Search {
+        /// Text to look for in version IDs
+        query: String,
+        /// Maximum number of versions to show
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+    /// Show the version manifest: latest versions and counts, or the whole manifest as JSON
+    Manifest {
+        /// Print the manifest as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show disk usage of libraries, assets, versions and instances
+    #[command(alias = "du")]
+    Disk,
+    /// Diagnose common environment problems
+    Doctor,
+    /// Generate shell completion scripts
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Print instance names, one per line (used by shell completions)
+    #[command(name = "__complete-instances", hide = true)]
+    CompleteInstances,
 }
 
 #[derive(Subcommand)]
 pub enum AuthCommands {
     /// Check authentication status
     Status,
+    /// Fetch and show the current profile using the cached login, offering to sign in again
+    Whoami,
     /// Clear cached authentication
     Clear,
     /// Force re-authentication
     Refresh,
 }
 
+#[derive(Subcommand)]
+pub enum VersionCommands {
+    /// Show a version's requirements and download size without preparing it
+    Info {
+        /// Version ID or alias such as latest-release
+        id: String,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum InstanceCommands {
     /// List all instances
-    List,
+    List {
+        /// Order to list instances in
+        #[arg(long, value_enum, default_value = "name")]
+        sort: InstanceSort,
+        /// Show when instances were last used in this strftime format instead of "2 hours ago"
+        #[arg(long, value_name = "FORMAT", value_parser = parse_time_format)]
+        time_format: Option<String>,
+    },
     /// Create a new instance
     Create {
         /// Instance name
         name: String,
         /// Minecraft version
-        version: String,
+        #[arg(required_unless_present = "from")]
+        version: Option<String>,
         /// Instance description
         #[arg(short, long)]
         description: Option<String>,
+        /// Take the Minecraft version and mod loader from a Modrinth or CurseForge pack (file or URL)
+        #[arg(long, value_name = "PATH_OR_URL", conflicts_with = "version")]
+        from: Option<String>,
+        /// Verify the pack against a checksum such as sha256:<hex> (sha1, sha256 or sha512)
+        #[arg(long, value_name = "ALGORITHM:HEX", requires = "from")]
+        checksum: Option<String>,
+        /// Replace an existing instance with the same name
+        #[arg(long)]
+        force: bool,
+        /// Carry the replaced instance's worlds over to the new one
+        #[arg(long, requires = "force")]
+        keep_saves: bool,
     },
     /// Delete an instance
     Delete {
@@ -100,6 +202,22 @@ pub enum InstanceCommands {
     Info {
         /// Instance name
         name: String,
+        /// strftime format for timestamps, shown in local time
+        #[arg(long, value_name = "FORMAT", value_parser = parse_time_format)]
+        time_format: Option<String>,
+    },
+    /// Open an instance's folder in the file manager
+    Open {
+        /// Instance name
+        name: String,
+    },
+    /// Print an instance's latest game log
+    Logs {
+        /// Instance name
+        name: String,
+        /// Keep printing new log output as the game writes it
+        #[arg(short, long)]
+        follow: bool,
     },
     /// Set instance memory allocation
     Memory {
@@ -108,12 +226,222 @@ pub enum InstanceCommands {
         /// Memory in MB
         memory: u32,
     },
+    /// Set environment variables for the game process
+    Env {
+        /// Instance name
+        name: String,
+        /// Variables as KEY=VALUE; use KEY= to remove one. Lists current variables when omitted
+        #[arg(value_parser = parse_env_var)]
+        vars: Vec<(String, String)>,
+    },
+    /// Launch the game through a wrapper command (e.g. gamemoderun, prime-run)
+    Wrapper {
+        /// Instance name
+        name: String,
+        /// Wrapper command and its arguments
+        #[arg(
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            required_unless_present = "clear"
+        )]
+        command: Vec<String>,
+        /// Remove the wrapper and launch Java directly
+        #[arg(long, conflicts_with = "command")]
+        clear: bool,
+    },
+    /// Zip an instance's config, saves, mods and settings (logs are left out)
+    #[command(alias = "archive")]
+    Backup {
+        /// Instance name
+        name: String,
+        /// Archive to write (defaults to <name>-<timestamp>.zip in the current directory)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Restore an instance from a backup archive
+    Restore {
+        /// Backup archive created by 'instance backup'
+        file: std::path::PathBuf,
+        /// Name for the restored instance (defaults to the original name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Manage an instance's mod loader
+    Loader {
+        #[command(subcommand)]
+        action: LoaderCommands,
+    },
+    /// Delete instances that have not been used for a number of days
+    Prune {
+        /// Delete instances last used (or, if never used, created) more than this many days ago
+        #[arg(long, value_name = "DAYS")]
+        older_than: u32,
+        /// List the instances that would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Options for preparing a version
+#[derive(Args, Debug, Default)]
+pub struct PrepareArgs {
+    /// Version to prepare (prompts for one when omitted in a terminal)
+    pub version: Option<String>,
+    /// Pick the version from an interactive list
+    #[arg(short, long, conflicts_with = "version")]
+    pub interactive: bool,
+    /// Prepare the latest release
+    #[arg(long, conflicts_with_all = ["version", "interactive", "latest_snapshot"])]
+    pub latest: bool,
+    /// Prepare the latest snapshot
+    #[arg(long, conflicts_with_all = ["version", "interactive"])]
+    pub latest_snapshot: bool,
+    /// Trust files shared with this already-prepared version instead of re-verifying them
+    #[arg(long, value_name = "VERSION")]
+    pub since_version: Option<String>,
+    /// Download into this directory (laid out like .minecraft) instead of the Minecraft directory
+    #[arg(long, value_name = "PATH")]
+    pub output_dir: Option<std::path::PathBuf>,
+    /// Reuse the official launcher's libraries and assets instead of downloading them into --output-dir
+    #[arg(long, requires = "output_dir", conflicts_with = "since_version")]
+    pub link_official: bool,
+    /// Only re-extract natives from the library jars already downloaded, without any downloads
+    #[arg(long, conflicts_with = "since_version")]
+    pub rebuild_natives: bool,
+    /// Print how much would be downloaded, without downloading
+    #[arg(long, conflicts_with = "rebuild_natives")]
+    pub estimate: bool,
+    /// Don't ask for confirmation before a large download
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Options for launching an instance
+#[derive(Args, Debug, Default)]
+pub struct LaunchArgs {
+    /// Skip file verification (faster launch)
+    #[arg(long)]
+    pub skip_verification: bool,
+    /// Launch without network access using already-prepared files and cached login
+    #[arg(long)]
+    pub offline: bool,
+    /// Print the resolved launch command without starting the game
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Write the resolved launch command to a file as a JSON array instead of starting the game
+    #[arg(long, value_name = "PATH", conflicts_with = "dry_run")]
+    pub emit_command: Option<std::path::PathBuf>,
+    /// Write a standalone script (.sh, or .bat/.ps1 on Windows) that launches the game
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["dry_run", "emit_command"])]
+    pub export_script: Option<std::path::PathBuf>,
+    /// Boot straight into a singleplayer world (quick play, 1.20+)
+    #[arg(long)]
+    pub world: Option<String>,
+    /// Join a multiplayer server on startup, as address[:port]
+    #[arg(long, value_name = "ADDRESS", conflicts_with = "world")]
+    pub connect: Option<String>,
+    /// Start the game in demo mode for this launch, overriding the instance's `demo`
+    #[arg(long, overrides_with = "no_demo")]
+    pub demo: bool,
+    /// Start the full game for this launch, even if the instance sets `demo`
+    #[arg(long, overrides_with = "demo")]
+    pub no_demo: bool,
+    /// Back up the instance's worlds before starting the game
+    #[arg(long)]
+    pub backup: bool,
+    /// Kill the game and fail if it is still running after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub launch_timeout: Option<u64>,
+    /// Wait for a keypress after the game exits so its status stays visible
+    #[arg(long)]
+    pub keep_open: bool,
+    /// Fail instead of using a newer Java when the exact required version is missing
+    #[arg(long)]
+    pub strict_java: bool,
+    /// Launch even if the instance looks like it is already running
+    #[arg(long)]
+    pub force: bool,
+    /// Re-fetch the player profile for a cached login, picking up name changes
+    #[arg(long, conflicts_with = "offline")]
+    pub refresh_profile: bool,
+}
+
+impl LaunchArgs {
+    /// Demo mode requested on the command line, if either `--demo` or `--no-demo` was given
+    pub fn demo_override(&self) -> Option<bool> {
+        match (self.demo, self.no_demo) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum LoaderCommands {
+    /// Install the Quilt loader into an instance
+    Quilt {
+        /// Instance name
+        name: String,
+        /// Loader version to install (defaults to the latest stable release)
+        #[arg(long)]
+        loader_version: Option<String>,
+    },
+    /// Remove an instance's mod loader so it launches vanilla Minecraft again
+    Remove {
+        /// Instance name
+        name: String,
+        /// Move the instance's mods folder aside to mods.disabled
+        #[arg(long)]
+        disable_mods: bool,
+    },
+    /// List available versions of a mod loader
+    Versions {
+        /// Mod loader to list
+        #[arg(value_enum)]
+        loader: LoaderKind,
+        /// Only show versions for this Minecraft version (required for Forge)
+        #[arg(long, value_name = "VERSION")]
+        mc_version: Option<String>,
+        /// Maximum number of versions to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum LoaderKind {
+    Fabric,
+    Forge,
+    Quilt,
+}
+
+/// Parses a `KEY=VALUE` environment variable assignment
+fn parse_env_var(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{s}'"))?;
+    if key.is_empty() || key.contains('\0') || value.contains('\0') {
+        return Err(format!("invalid environment variable '{s}'"));
+    }
+    Ok((key.to_string(), value.to_string()))
 }
 
 #[derive(Subcommand)]
 pub enum JavaCommands {
     /// List available Java installations
-    List,
+    List {
+        /// Print the installations as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Scan for Java installations again instead of using the cached results
+    Rescan,
+    /// Stop using a detected Java installation until the next rescan
+    Remove {
+        /// Major version (e.g. 17) or path of the installation
+        target: String,
+    },
     /// Show recommended Java version for a Minecraft version
     Recommend {
         /// Minecraft version
@@ -141,4 +469,44 @@ pub enum SortOrder {
     OldestFirst,
     /// Alphabetical order
     Alphabetical,
+    /// By parsed release timestamp, newest first
+    ReleaseDate,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum InstanceSort {
+    /// Alphabetical by name (default)
+    Name,
+    /// Most recently created first
+    Created,
+    /// Most recently used first; never-used instances last
+    LastUsed,
+    /// Newest Minecraft version first
+    Version,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum LogFormat {
+    /// Colored, human-readable output (default)
+    Text,
+    /// One JSON object per log event
+    Json,
+}
+
+/// Check that a strftime format is valid, so formatting a timestamp with it can't fail
+fn parse_time_format(s: &str) -> Result<String, String> {
+    use chrono::format::{Item, StrftimeItems};
+
+    if StrftimeItems::new(s).any(|item| matches!(item, Item::Error)) {
+        return Err(format!(
+            "invalid time format '{s}' (see strftime, e.g. %Y-%m-%d %H:%M)"
+        ));
+    }
+    Ok(s.to_string())
+}
+
+/// Parse a target architecture name into its canonical form
+fn parse_target_arch(s: &str) -> Result<&'static str, String> {
+    crate::launcher::normalize_arch(s)
+        .ok_or_else(|| format!("unknown architecture '{s}' (expected x86_64, aarch64 or x86)"))
 }
@@ -1,12 +1,16 @@
 use crate::cli::AuthCommands;
-use tracing::info;
+use std::io::IsTerminal;
+use tracing::{info, warn};
 
 /// Handles authentication-related commands.
 ///
+/// Returns the process exit code: `1` from `status` when no valid login is cached, or from
+/// `whoami` when signing in again is declined, so scripts can branch on it, and `0` otherwise.
+///
 /// # Errors
 ///
 /// Returns an error if authentication storage operations fail or if authentication fails.
-pub async fn handle_auth_command(action: AuthCommands) -> crate::error::Result<()> {
+pub async fn handle_auth_command(action: AuthCommands) -> crate::error::Result<i32> {
     let storage = crate::auth::storage::AuthStorage::new()?;
 
     match action {
@@ -15,12 +19,18 @@ pub async fn handle_auth_command(action: AuthCommands) -> crate::error::Result<(
                 info!("✓ Authentication: Valid");
                 info!("  Player: {}", cached_auth.profile.name);
                 info!("  UUID: {}", cached_auth.profile.id);
+                warn_pending_actions(&cached_auth.profile);
                 // Don't log the token for security
             } else {
                 info!("❌ No valid authentication found");
                 info!("  Run 'Redstonium launch <instance>' to authenticate");
+                return Ok(1);
             }
         }
+        AuthCommands::Whoami => {
+            let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+            return show_fresh_profile(storage.load_auth().await?, interactive).await;
+        }
         AuthCommands::Clear => {
             storage.clear_cache().await?;
             info!("✓ Authentication cache cleared");
@@ -28,7 +38,7 @@ pub async fn handle_auth_command(action: AuthCommands) -> crate::error::Result<(
         AuthCommands::Refresh => {
             info!("Clearing cache and forcing re-authentication...");
             storage.clear_cache().await?;
-            let auth_result = crate::auth::authenticate().await?;
+            let auth_result = crate::auth::authenticate(time::Duration::ZERO).await?;
             info!(
                 "✓ Re-authentication successful for {}",
                 auth_result.profile.name
@@ -36,5 +46,105 @@ pub async fn handle_auth_command(action: AuthCommands) -> crate::error::Result<(
         }
     }
 
-    Ok(())
+    Ok(0)
+}
+
+/// Re-fetches the player profile with the cached token and prints it
+///
+/// Offers to sign in again when nothing valid is cached or the cached token is rejected,
+/// returning exit code `1` if that is declined or there's no terminal to ask in.
+async fn show_fresh_profile(
+    cached_auth: Option<crate::auth::AuthResult>,
+    interactive: bool,
+) -> crate::error::Result<i32> {
+    let (cached_auth, signed_in) = match cached_auth {
+        Some(cached_auth) => (cached_auth, false),
+        None => {
+            info!("❌ No valid authentication found");
+            let Some(auth) = sign_in_again(interactive).await? else {
+                return Ok(1);
+            };
+            (auth, true)
+        }
+    };
+
+    let profile = match crate::auth::fetch_profile(&cached_auth.access_token).await {
+        Ok(profile) => profile,
+        Err(e) if signed_in => return Err(e),
+        Err(e) => {
+            warn!("Failed to fetch profile with the cached login: {e}");
+            let Some(auth) = sign_in_again(interactive).await? else {
+                return Ok(1);
+            };
+            crate::auth::fetch_profile(&auth.access_token).await?
+        }
+    };
+
+    info!("Player: {}", profile.name);
+    info!("  UUID: {}", profile.id);
+    if profile.name != cached_auth.profile.name {
+        info!("  (previously cached as {})", cached_auth.profile.name);
+    }
+    warn_pending_actions(&profile);
+
+    let skins = profile.skins.as_deref().unwrap_or_default();
+    info!("  Skins: {}", skins.len());
+    for skin in skins {
+        info!("    {} ({}) - {}", skin.variant, skin.state, skin.url);
+    }
+
+    let capes = profile.capes.as_deref().unwrap_or_default();
+    info!("  Capes: {}", capes.len());
+    for cape in capes {
+        info!("    {} ({})", cape.alias, cape.state);
+    }
+
+    Ok(0)
+}
+
+/// Signs in from scratch if the user agrees, returning `None` when they don't
+///
+/// Without a terminal to ask in, nothing is attempted.
+async fn sign_in_again(interactive: bool) -> crate::error::Result<Option<crate::auth::AuthResult>> {
+    let confirmed = interactive
+        && dialoguer::Confirm::new()
+            .with_prompt("Sign in again now?")
+            .default(true)
+            .interact_on(&dialoguer::console::Term::stderr())
+            .map_err(|e| {
+                crate::error::RustifiedError::generic(format!("Confirmation failed: {e}"))
+            })?;
+    if !confirmed {
+        info!("  Run 'Redstonium auth refresh' to sign in again");
+        return Ok(None);
+    }
+
+    crate::auth::storage::AuthStorage::new()?
+        .clear_cache()
+        .await?;
+    crate::auth::authenticate(time::Duration::ZERO)
+        .await
+        .map(Some)
+}
+
+/// Warns about pending profile actions that may block multiplayer
+fn warn_pending_actions(profile: &crate::auth::MinecraftProfile) {
+    let actions = profile.pending_actions();
+    if !actions.is_empty() {
+        warn!(
+            "⚠ Account has pending actions that may block multiplayer: {}",
+            actions.join(", ")
+        );
+        warn!("  Resolve them at https://www.minecraft.net/msaprofile");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_whoami_without_login_fails_when_not_interactive() {
+        assert_eq!(show_fresh_profile(None, false).await.unwrap(), 1);
+    }
 }
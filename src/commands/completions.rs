@@ -0,0 +1,70 @@
+use crate::cli::Cli;
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+/// Subcommands whose positional argument is an instance name
+const INSTANCE_SUBCOMMANDS: [&str; 9] = [
+    "launch", "info", "delete", "memory", "env", "wrapper", "backup", "open", "logs",
+];
+
+/// Writes a completion script for the given shell to stdout.
+///
+/// Bash and fish scripts are extended to complete instance names through the
+/// hidden `__complete-instances` helper.
+pub fn print_completions(shell: Shell) {
+    let mut command = Cli::command();
+    let bin_name = command.get_name().to_string();
+    clap_complete::generate(
+        shell,
+        &mut command,
+        bin_name.clone(),
+        &mut std::io::stdout(),
+    );
+
+    match shell {
+        Shell::Bash => print!("{}", bash_instance_completion(&bin_name)),
+        Shell::Fish => print!("{}", fish_instance_completion(&bin_name)),
+        _ => {}
+    }
+}
+
+/// Prints the names of all instances, one per line.
+///
+/// # Errors
+///
+/// Returns an error if the Minecraft directory cannot be determined.
+pub fn print_instance_names() -> crate::error::Result<()> {
+    let minecraft_dir = crate::launcher::MinecraftDir::new()?;
+    for name in crate::launcher::InstanceManager::instance_names(&minecraft_dir) {
+        println!("{name}");
+    }
+    Ok(())
+}
+
+/// Wraps the generated bash completion function to offer instance names
+fn bash_instance_completion(bin_name: &str) -> String {
+    let function = bin_name.replace('-', "__");
+    let subcommands = INSTANCE_SUBCOMMANDS.join("|");
+    format!(
+        r#"
+_{function}_instances() {{
+    case "${{COMP_WORDS[COMP_CWORD-1]}}" in
+        {subcommands})
+            COMPREPLY=( $(compgen -W "$({bin_name} __complete-instances 2>/dev/null)" -- "${{COMP_WORDS[COMP_CWORD]}}") )
+            return 0
+            ;;
+    esac
+    _{function} "$@"
+}}
+complete -F _{function}_instances -o bashdefault -o default {bin_name}
+"#
+    )
+}
+
+/// Adds a fish completion rule that offers instance names
+fn fish_instance_completion(bin_name: &str) -> String {
+    let subcommands = INSTANCE_SUBCOMMANDS.join(" ");
+    format!(
+        "complete -c {bin_name} -n \"__fish_seen_subcommand_from {subcommands}\" -f -a \"({bin_name} __complete-instances 2>/dev/null)\"\n"
+    )
+}
@@ -0,0 +1,56 @@
+use crate::error::{Result, ResultExt};
+use crate::launcher::{InstanceManager, Launcher, MinecraftDir};
+use tracing::info;
+
+/// Reports disk usage of shared files, each version and each instance.
+///
+/// # Errors
+///
+/// Returns an error if the directory walk cannot be completed.
+pub async fn show_disk_usage(launcher: &Launcher) -> Result<()> {
+    let minecraft_dir = launcher.minecraft_dir.clone();
+    let mut entries = tokio::task::spawn_blocking(move || measure(&minecraft_dir))
+        .await
+        .context("Failed to measure disk usage")?;
+
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    info!(
+        "Disk usage in {}:",
+        launcher.minecraft_dir.base_path.display()
+    );
+    for (name, size) in &entries {
+        info!("  {:>10}  {name}", super::format_size(*size));
+    }
+
+    let total: u64 = entries.iter().map(|(_, size)| size).sum();
+    info!("");
+    info!("  {:>10}  total", super::format_size(total));
+    Ok(())
+}
+
+/// Sizes of `libraries/`, `assets/`, each version and each instance
+fn measure(minecraft_dir: &MinecraftDir) -> Vec<(String, u64)> {
+    let mut entries = vec![
+        (
+            "libraries/".to_string(),
+            MinecraftDir::directory_size(&minecraft_dir.libraries_dir()),
+        ),
+        (
+            "assets/".to_string(),
+            MinecraftDir::directory_size(&minecraft_dir.assets_dir()),
+        ),
+    ];
+
+    for id in minecraft_dir.installed_version_ids() {
+        let size = MinecraftDir::directory_size(&minecraft_dir.version_dir(&id));
+        entries.push((format!("versions/{id}/"), size));
+    }
+
+    for name in InstanceManager::instance_names(minecraft_dir) {
+        let size = MinecraftDir::directory_size(&minecraft_dir.instances_dir().join(&name));
+        entries.push((format!("instances/{name}/"), size));
+    }
+
+    entries
+}
@@ -0,0 +1,241 @@
+use crate::launcher::{JavaManager, Launcher};
+use std::path::Path;
+use tracing::{error, info, warn};
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// A named diagnostic check and its result
+struct CheckResult {
+    name: &'static str,
+    status: CheckStatus,
+    detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Runs environment diagnostics and prints a pass/warn/fail checklist.
+///
+/// The command itself never fails; problems are reported in the checklist.
+pub async fn run_doctor(launcher: &Launcher) {
+    info!("Running environment diagnostics...");
+    info!("");
+
+    let latest_release = launcher
+        .file_manager
+        .get_version_manifest()
+        .await
+        .ok()
+        .map(|manifest| manifest.latest.release.clone());
+
+    let results = vec![
+        check_java_installations(launcher.java_manager().await),
+        check_java_for_latest(launcher.java_manager().await, latest_release.as_deref()),
+        check_minecraft_dir(&launcher.minecraft_dir.base_path),
+        check_libc(),
+        check_network(launcher).await,
+        check_cache_dir(),
+        check_client_id(),
+    ];
+
+    for result in &results {
+        match result.status {
+            CheckStatus::Pass => info!("✓ {}: {}", result.name, result.detail),
+            CheckStatus::Warn => warn!("⚠ {}: {}", result.name, result.detail),
+            CheckStatus::Fail => error!("❌ {}: {}", result.name, result.detail),
+        }
+    }
+
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    info!("");
+    info!(
+        "{} passed, {} warnings, {} failed",
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail)
+    );
+}
+
+/// Reports detected Java installations
+fn check_java_installations(java_manager: &JavaManager) -> CheckResult {
+    if java_manager.installations.is_empty() {
+        return CheckResult::new(
+            "Java installations",
+            CheckStatus::Fail,
+            "none found; install Java or set JAVA_HOME",
+        );
+    }
+
+    let mut installations: Vec<_> = java_manager.installations.values().collect();
+    installations.sort_by_key(|installation| installation.major_version);
+    let list = installations
+        .iter()
+        .map(|installation| match installation.details() {
+            Some(details) => format!("Java {} ({details})", installation.major_version),
+            None => format!("Java {}", installation.major_version),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    CheckResult::new("Java installations", CheckStatus::Pass, list)
+}
+
+/// Checks whether the Java required by the latest release is available
+fn check_java_for_latest(java_manager: &JavaManager, latest: Option<&str>) -> CheckResult {
+    const NAME: &str = "Java for latest release";
+
+    let Some(latest) = latest else {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            "could not determine the latest release (manifest unavailable)",
+        );
+    };
+
+    let required = JavaManager::get_required_java_version(latest);
+    if java_manager.installations.contains_key(&required) {
+        CheckResult::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("Minecraft {latest} requires Java {required}, which is installed"),
+        )
+    } else if java_manager
+        .installations
+        .keys()
+        .any(|major| *major > required)
+    {
+        CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("Minecraft {latest} requires Java {required}; only newer versions found"),
+        )
+    } else {
+        CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("Minecraft {latest} requires Java {required}, which is not installed"),
+        )
+    }
+}
+
+/// Checks that the Minecraft directory is writable
+fn check_minecraft_dir(base_path: &Path) -> CheckResult {
+    match check_dir_writable(base_path) {
+        Ok(()) => CheckResult::new(
+            "Minecraft directory",
+            CheckStatus::Pass,
+            format!("{} is writable", base_path.display()),
+        ),
+        Err(e) => CheckResult::new(
+            "Minecraft directory",
+            CheckStatus::Fail,
+            format!("{} is not writable: {e}", base_path.display()),
+        ),
+    }
+}
+
+/// Warns when running on musl, where Mojang's glibc natives will not load
+fn check_libc() -> CheckResult {
+    if crate::launcher::is_musl() {
+        CheckResult::new(
+            "C library",
+            CheckStatus::Warn,
+            "musl detected; Minecraft's natives need glibc (try gcompat)",
+        )
+    } else {
+        CheckResult::new(
+            "C library",
+            CheckStatus::Pass,
+            "compatible with Minecraft natives",
+        )
+    }
+}
+
+/// Probes the Mojang version manifest endpoint
+async fn check_network(launcher: &Launcher) -> CheckResult {
+    match launcher.file_manager.probe_manifest_endpoint().await {
+        Ok(status) if status.is_success() => CheckResult::new(
+            "Mojang endpoints",
+            CheckStatus::Pass,
+            format!("version manifest reachable (HTTP {status})"),
+        ),
+        Ok(status) => CheckResult::new(
+            "Mojang endpoints",
+            CheckStatus::Warn,
+            format!("version manifest returned HTTP {status}"),
+        ),
+        Err(e) => CheckResult::new(
+            "Mojang endpoints",
+            CheckStatus::Fail,
+            format!("version manifest unreachable: {e}"),
+        ),
+    }
+}
+
+/// Checks that the auth cache directory exists and is writable
+fn check_cache_dir() -> CheckResult {
+    let storage = match crate::auth::storage::AuthStorage::new() {
+        Ok(storage) => storage,
+        Err(e) => {
+            return CheckResult::new("Cache directory", CheckStatus::Fail, e.to_string());
+        }
+    };
+
+    let Some(cache_dir) = storage.cache_file_path().parent() else {
+        return CheckResult::new(
+            "Cache directory",
+            CheckStatus::Fail,
+            "could not determine cache directory",
+        );
+    };
+
+    match check_dir_writable(cache_dir) {
+        Ok(()) => CheckResult::new(
+            "Cache directory",
+            CheckStatus::Pass,
+            format!("{} is writable", cache_dir.display()),
+        ),
+        Err(e) => CheckResult::new(
+            "Cache directory",
+            CheckStatus::Fail,
+            format!("{} is not writable: {e}", cache_dir.display()),
+        ),
+    }
+}
+
+/// Reports whether a custom Microsoft client ID is configured
+fn check_client_id() -> CheckResult {
+    let env_name = crate::auth::CLIENT_ID_ENV;
+    if std::env::var(env_name).is_ok_and(|value| !value.trim().is_empty()) {
+        CheckResult::new(
+            "Microsoft client ID",
+            CheckStatus::Pass,
+            format!("{env_name} is set and will be used for sign-in"),
+        )
+    } else {
+        CheckResult::new(
+            "Microsoft client ID",
+            CheckStatus::Pass,
+            format!("{env_name} is not set; using the built-in client ID"),
+        )
+    }
+}
+
+/// Writes and removes a probe file to verify a directory is writable
+fn check_dir_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(".redstonium-write-test");
+    std::fs::write(&probe, b"ok")?;
+    std::fs::remove_file(&probe)
+}
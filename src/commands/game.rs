@@ -1,6 +1,8 @@
-use crate::cli::{SortOrder, VersionTypeFilter};
+use crate::cli::{LaunchArgs, PrepareArgs, SortOrder, VersionTypeFilter};
+use crate::error::ResultExt;
 use crate::launcher;
-use tracing::{error, info};
+use std::io::IsTerminal;
+use tracing::{error, info, warn};
 
 /// Options for listing Minecraft versions
 #[derive(Debug)]
@@ -12,6 +14,34 @@ pub struct ListVersionsOptions {
     pub filter: Option<String>,
     pub show_installed: bool,
     pub sort: SortOrder,
+    pub since: Option<chrono::NaiveDate>,
+    pub until: Option<chrono::NaiveDate>,
+    pub count: bool,
+    pub exclude_experimental: bool,
+    pub show_java: bool,
+    pub fetch_java: bool,
+    pub latest_per_minor: bool,
+}
+
+impl Default for ListVersionsOptions {
+    fn default() -> Self {
+        Self {
+            types: Vec::new(),
+            releases_only: false,
+            snapshots_only: false,
+            limit: 10,
+            filter: None,
+            show_installed: false,
+            sort: SortOrder::NewestFirst,
+            since: None,
+            until: None,
+            count: false,
+            exclude_experimental: false,
+            show_java: false,
+            fetch_java: false,
+            latest_per_minor: false,
+        }
+    }
 }
 
 /// Lists available Minecraft versions.
@@ -27,8 +57,348 @@ pub async fn list_versions(
     info!("Fetching available Minecraft versions...");
     let manifest = launcher.file_manager.get_version_manifest().await?;
 
-    let mut versions = manifest.versions.clone();
+    let mut versions = filter_and_sort_versions(manifest.versions.clone(), &options);
+
+    if options.count {
+        print_version_counts(launcher, &versions);
+        return Ok(());
+    }
+
+    // Apply limit
+    versions.truncate(options.limit);
+
+    // Show hint about filtering options if using default settings
+    let is_using_defaults = options.types.is_empty()
+        && !options.releases_only
+        && !options.snapshots_only
+        && options.filter.is_none()
+        && options.limit == 10
+        && !options.show_installed
+        && matches!(options.sort, SortOrder::NewestFirst)
+        && options.since.is_none()
+        && options.until.is_none()
+        && !options.exclude_experimental
+        && !options.show_java
+        && !options.latest_per_minor;
+
+    if is_using_defaults {
+        info!(
+            "💡 Tip: Use 'Redstonium list --help' to see filtering options like --types, --filter, --sort, and more!"
+        );
+    }
+
+    info!("Available Minecraft versions:");
+    info!("Latest release: {}", manifest.latest.release);
+    info!("Latest snapshot: {}", manifest.latest.snapshot);
+
+    print_active_filters(&options);
+
+    if !is_using_defaults {
+        info!("Sort order: {:?}", options.sort);
+    }
+    info!(
+        "Showing {} of {} total versions",
+        versions.len().min(options.limit),
+        manifest.versions.len()
+    );
+    info!("");
+
+    let java_versions = if options.show_java {
+        required_java_versions(launcher, &versions, options.fetch_java).await
+    } else {
+        Vec::new()
+    };
+
+    for (index, version) in versions.iter().enumerate() {
+        let installed_marker =
+            if options.show_installed && launcher.minecraft_dir.is_version_installed(&version.id) {
+                "✓ "
+            } else if options.show_installed {
+                "  "
+            } else {
+                ""
+            };
+
+        let java = java_versions
+            .get(index)
+            .map(|major| format!(" - requires Java {major}"))
+            .unwrap_or_default();
+
+        info!(
+            "{}{} ({:?}) - {}{java}",
+            installed_marker, version.id, version.version_type, version.release_time
+        );
+    }
+
+    Ok(())
+}
+
+/// Version JSONs fetched at once for `--fetch-java`
+const VERSION_INFO_FETCHES: usize = 8;
+
+/// Required Java major version for each version, in order
+///
+/// Estimated from the version number unless `fetch` is set, in which case each
+/// version's JSON (read from disk when prepared) supplies its declared requirement.
+/// Estimates are marked "(estimated)", as in `version info`.
+async fn required_java_versions(
+    launcher: &launcher::Launcher,
+    versions: &[launcher::VersionEntry],
+    fetch: bool,
+) -> Vec<String> {
+    use futures_util::stream::{self, StreamExt};
+
+    stream::iter(versions)
+        .map(|version| async move {
+            let declared = if fetch {
+                launcher
+                    .file_manager
+                    .get_version_info_cached(&version.id, &launcher.minecraft_dir)
+                    .await
+                    .ok()
+                    .and_then(|info| info.java_version)
+                    .map(|java| java.major_version)
+            } else {
+                None
+            };
+            declared.map_or_else(
+                || {
+                    format!(
+                        "{} (estimated)",
+                        launcher::JavaManager::get_required_java_version(&version.id)
+                    )
+                },
+                |major| major.to_string(),
+            )
+        })
+        .buffered(VERSION_INFO_FETCHES)
+        .collect()
+        .await
+}
+
+/// Prints summary counts for the filtered versions
+fn print_version_counts(launcher: &launcher::Launcher, versions: &[launcher::VersionEntry]) {
+    let releases = versions
+        .iter()
+        .filter(|v| matches!(v.version_type, launcher::VersionType::Release))
+        .count();
+    let snapshots = versions
+        .iter()
+        .filter(|v| matches!(v.version_type, launcher::VersionType::Snapshot))
+        .count();
+    let installed = versions
+        .iter()
+        .filter(|v| launcher.minecraft_dir.is_version_installed(&v.id))
+        .count();
+
+    info!("Total: {}", versions.len());
+    info!("Releases: {releases}");
+    info!("Snapshots: {snapshots}");
+    info!("Installed: {installed}");
+}
+
+/// Lists the versions that fuzzy-match a query, best matches first.
+///
+/// # Errors
+///
+/// Returns an error if fetching the version manifest fails.
+pub async fn search_versions(
+    launcher: &launcher::Launcher,
+    query: &str,
+    limit: usize,
+) -> crate::error::Result<()> {
+    let manifest = launcher.file_manager.get_version_manifest().await?;
+    let mut matches = fuzzy_match_versions(&manifest.versions, query);
+
+    if matches.is_empty() {
+        info!("No versions match \"{query}\"");
+        info!("💡 Use 'Redstonium list' to browse all versions");
+        return Ok(());
+    }
+
+    info!(
+        "{} version(s) match \"{query}\", showing {}:",
+        matches.len(),
+        matches.len().min(limit)
+    );
+    matches.truncate(limit);
+    for version in matches {
+        let installed = if launcher.minecraft_dir.is_version_installed(&version.id) {
+            " [installed]"
+        } else {
+            ""
+        };
+        info!(
+            "{} ({:?}) - {}{installed}",
+            version.id, version.version_type, version.release_time
+        );
+    }
+    Ok(())
+}
+
+/// Versions whose ID fuzzy-matches the query, best score first
+///
+/// Equal scores keep the manifest's newest-first order.
+fn fuzzy_match_versions<'a>(
+    versions: &'a [launcher::VersionEntry],
+    query: &str,
+) -> Vec<&'a launcher::VersionEntry> {
+    use fuzzy_matcher::FuzzyMatcher;
+
+    let matcher = fuzzy_matcher::skim::SkimMatcherV2::default().ignore_case();
+    let mut scored: Vec<_> = versions
+        .iter()
+        .filter_map(|version| {
+            matcher
+                .fuzzy_match(&version.id, query)
+                .map(|score| (score, version))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, version)| version).collect()
+}
+
+/// Prints the version manifest, summarized or as JSON.
+///
+/// The JSON holds the fields the launcher reads, not every field Mojang sends.
+///
+/// # Errors
+///
+/// Returns an error if fetching or serializing the version manifest fails.
+pub async fn show_manifest(launcher: &launcher::Launcher, json: bool) -> crate::error::Result<()> {
+    let manifest = launcher.file_manager.get_version_manifest().await?;
+
+    if json {
+        let json = serde_json::to_string_pretty(manifest)
+            .context("Failed to serialize the version manifest")?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    let count = |version_type: fn(&launcher::VersionType) -> bool| {
+        manifest
+            .versions
+            .iter()
+            .filter(|v| version_type(&v.version_type))
+            .count()
+    };
+    info!("Latest release: {}", manifest.latest.release);
+    info!("Latest snapshot: {}", manifest.latest.snapshot);
+    info!("Versions: {}", manifest.versions.len());
+    info!(
+        "Releases: {}",
+        count(|t| matches!(t, launcher::VersionType::Release))
+    );
+    info!(
+        "Snapshots: {}",
+        count(|t| matches!(t, launcher::VersionType::Snapshot))
+    );
+    info!(
+        "Old betas: {}",
+        count(|t| matches!(t, launcher::VersionType::OldBeta))
+    );
+    info!(
+        "Old alphas: {}",
+        count(|t| matches!(t, launcher::VersionType::OldAlpha))
+    );
+    Ok(())
+}
+
+/// Prints a summary of the filters applied to the version list
+fn print_active_filters(options: &ListVersionsOptions) {
+    if let Some(filter_pattern) = &options.filter {
+        info!("Filtered by: \"{filter_pattern}\"");
+    }
+
+    if !options.types.is_empty() {
+        let type_names: Vec<String> = options.types.iter().map(|t| format!("{t:?}")).collect();
+        info!("Types: {}", type_names.join(", "));
+    } else if options.releases_only {
+        info!("Showing only: Release versions");
+    } else if options.snapshots_only {
+        info!("Showing only: Snapshot versions");
+    }
+
+    if options.exclude_experimental {
+        info!("Hiding experimental snapshots");
+    }
+
+    if options.latest_per_minor {
+        info!("Showing only the newest patch of each release line");
+    }
+
+    match (options.since, options.until) {
+        (Some(since), Some(until)) => info!("Released between {since} and {until}"),
+        (Some(since), None) => info!("Released since {since}"),
+        (None, Some(until)) => info!("Released until {until}"),
+        (None, None) => {}
+    }
+}
+
+/// Retains only the newest patch of each `major.minor` release line.
+///
+/// Within a line the highest patch wins, then the latest release date, so `1.20.5`
+/// beats `1.20.5-pre1`. Versions without a release number, like weekly snapshots, are kept.
+fn retain_latest_per_minor(versions: &mut Vec<launcher::VersionEntry>) {
+    let mut newest: std::collections::HashMap<(u32, u32), &launcher::VersionEntry> =
+        std::collections::HashMap::new();
+    for version in versions.iter() {
+        let Some((major, minor, _)) = version.release_number() else {
+            continue;
+        };
+        let rank = |v: &launcher::VersionEntry| (v.release_number(), v.release_date());
+        newest
+            .entry((major, minor))
+            .and_modify(|best| {
+                if rank(version) > rank(best) {
+                    *best = version;
+                }
+            })
+            .or_insert(version);
+    }
+
+    let keep: std::collections::HashSet<String> =
+        newest.values().map(|version| version.id.clone()).collect();
+    versions.retain(|v| v.release_number().is_none() || keep.contains(&v.id));
+}
+
+/// Retains only versions released within the inclusive date range.
+///
+/// Versions with a missing or malformed release time are excluded with a warning.
+fn retain_release_date_range(
+    versions: &mut Vec<launcher::VersionEntry>,
+    since: Option<chrono::NaiveDate>,
+    until: Option<chrono::NaiveDate>,
+) {
+    if since.is_none() && until.is_none() {
+        return;
+    }
+
+    let mut malformed = Vec::new();
+    versions.retain(|v| {
+        let Some(date) = v.release_date().map(|d| d.date_naive()) else {
+            malformed.push(v.id.clone());
+            return false;
+        };
+        since.is_none_or(|since| date >= since) && until.is_none_or(|until| date <= until)
+    });
 
+    if !malformed.is_empty() {
+        warn!(
+            "Excluded {} version(s) with an unreadable release date: {}",
+            malformed.len(),
+            malformed.join(", ")
+        );
+    }
+}
+
+/// Applies the type and text filters and the sort order from the list options.
+///
+/// Shared by `list` and the interactive version picker.
+fn filter_and_sort_versions(
+    mut versions: Vec<launcher::VersionEntry>,
+    options: &ListVersionsOptions,
+) -> Vec<launcher::VersionEntry> {
     // Handle filtering based on the new options
     if !options.types.is_empty() {
         // Filter by specific types provided
@@ -56,103 +426,277 @@ pub async fn list_versions(
         versions.retain(|v| matches!(v.version_type, launcher::VersionType::Snapshot));
     }
 
+    if options.exclude_experimental {
+        versions.retain(|v| !v.is_experimental());
+    }
+
     // Apply text filter if provided
     if let Some(filter_pattern) = &options.filter {
         let pattern = filter_pattern.to_lowercase();
         versions.retain(|v| v.id.to_lowercase().contains(&pattern));
     }
 
-    // Sort versions according to the specified order
+    retain_release_date_range(&mut versions, options.since, options.until);
+
+    if options.latest_per_minor {
+        retain_latest_per_minor(&mut versions);
+    }
+
+    // Sort versions according to the specified order. The manifest isn't
+    // guaranteed to be chronological across version types, so date-based
+    // orders use the parsed release time.
     match options.sort {
-        SortOrder::NewestFirst => {
-            // Already sorted newest first in the manifest, no change needed
+        SortOrder::NewestFirst | SortOrder::ReleaseDate => {
+            sort_by_release_date(&mut versions, true);
         }
         SortOrder::OldestFirst => {
-            versions.reverse();
+            sort_by_release_date(&mut versions, false);
         }
         SortOrder::Alphabetical => {
             versions.sort_by(|a, b| a.id.cmp(&b.id));
         }
     }
 
-    // Apply limit
-    versions.truncate(options.limit);
+    versions
+}
 
-    // Show hint about filtering options if using default settings
-    let is_using_defaults = options.types.is_empty()
-        && !options.releases_only
-        && !options.snapshots_only
-        && options.filter.is_none()
-        && options.limit == 10
-        && !options.show_installed
-        && matches!(options.sort, SortOrder::NewestFirst);
+/// Sorts versions by release timestamp; unparseable dates always go last
+fn sort_by_release_date(versions: &mut [launcher::VersionEntry], newest_first: bool) {
+    versions.sort_by_cached_key(|v| {
+        let timestamp = v.release_date().map(|date| date.timestamp());
+        let ordered = if newest_first {
+            timestamp.map(std::ops::Neg::neg)
+        } else {
+            timestamp
+        };
+        (ordered.is_none(), ordered)
+    });
+}
 
-    if is_using_defaults {
-        info!(
-            "💡 Tip: Use 'Redstonium list --help' to see filtering options like --types, --filter, --sort, and more!"
-        );
+/// Determines which version `prepare` should download.
+///
+/// An explicit version is used as-is. Otherwise an interactive picker is shown
+/// when requested or when running in a terminal; scripted usage without a
+/// version is rejected.
+///
+/// # Errors
+///
+/// Returns an error if no version was given in a non-interactive context, if
+/// fetching the manifest fails, or if the selection is cancelled.
+pub async fn select_prepare_version(
+    launcher: &launcher::Launcher,
+    version: Option<String>,
+    interactive: bool,
+) -> crate::error::Result<String> {
+    if let Some(version) = version {
+        return Ok(version);
     }
 
-    info!("Available Minecraft versions:");
-    info!("Latest release: {}", manifest.latest.release);
-    info!("Latest snapshot: {}", manifest.latest.snapshot);
-
-    if let Some(filter_pattern) = &options.filter {
-        info!("Filtered by: \"{filter_pattern}\"");
+    let is_terminal = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    if !is_terminal {
+        if interactive {
+            return Err(crate::error::GameError::invalid_version(
+                "Interactive version selection requires a terminal",
+            )
+            .into());
+        }
+        return Err(crate::error::GameError::invalid_version(
+            "No version specified. Usage: Redstonium prepare <version> (or --interactive in a terminal)",
+        )
+        .into());
     }
 
-    if !options.types.is_empty() {
-        let type_names: Vec<String> = options.types.iter().map(|t| format!("{t:?}")).collect();
-        info!("Types: {}", type_names.join(", "));
-    } else if options.releases_only {
-        info!("Showing only: Release versions");
-    } else if options.snapshots_only {
-        info!("Showing only: Snapshot versions");
-    }
+    pick_version_interactively(launcher).await
+}
 
-    if !is_using_defaults {
-        info!("Sort order: {:?}", options.sort);
-    }
-    info!(
-        "Showing {} of {} total versions",
-        versions.len().min(options.limit),
-        manifest.versions.len()
-    );
-    info!("");
+/// Shows a selectable list of recent releases and snapshots
+async fn pick_version_interactively(launcher: &launcher::Launcher) -> crate::error::Result<String> {
+    const PICKER_LIMIT: usize = 20;
 
-    for version in &versions {
-        let installed_marker =
-            if options.show_installed && launcher.minecraft_dir.is_version_installed(&version.id) {
-                "✓ "
-            } else if options.show_installed {
-                "  "
+    let manifest = launcher.file_manager.get_version_manifest().await?;
+    let options = ListVersionsOptions {
+        types: vec![VersionTypeFilter::Release, VersionTypeFilter::Snapshot],
+        limit: PICKER_LIMIT,
+        show_installed: true,
+        ..ListVersionsOptions::default()
+    };
+    let mut versions = filter_and_sort_versions(manifest.versions.clone(), &options);
+    versions.truncate(options.limit);
+
+    let items: Vec<String> = versions
+        .iter()
+        .map(|v| {
+            let installed = if launcher.minecraft_dir.is_version_installed(&v.id) {
+                " [installed]"
             } else {
                 ""
             };
+            format!("{} ({:?}){installed}", v.id, v.version_type)
+        })
+        .collect();
 
-        info!(
-            "{}{} ({:?}) - {}",
-            installed_marker, version.id, version.version_type, version.release_time
-        );
+    let selection = dialoguer::Select::new()
+        .with_prompt("Select a Minecraft version to prepare")
+        .items(&items)
+        .default(0)
+        .interact_opt()
+        .map_err(|e| {
+            crate::error::RustifiedError::generic(format!("Version selection failed: {e}"))
+        })?;
+
+    let index = selection
+        .ok_or_else(|| crate::error::GameError::invalid_version("Version selection cancelled"))?;
+    Ok(versions[index].id.clone())
+}
+
+/// Downloads at least this large ask for confirmation in a terminal
+const LARGE_DOWNLOAD: u64 = 1024 * 1024 * 1024;
+
+/// Asks whether to go ahead with a large download
+///
+/// Without a terminal to ask in, the download goes ahead.
+fn confirm_download(bytes: u64) -> crate::error::Result<bool> {
+    if !(std::io::stdin().is_terminal() && std::io::stderr().is_terminal()) {
+        return Ok(true);
     }
 
+    dialoguer::Confirm::new()
+        .with_prompt(format!(
+            "This will download about {}. Continue?",
+            super::format_size(bytes)
+        ))
+        .default(true)
+        .interact_on(&dialoguer::console::Term::stderr())
+        .map_err(|e| crate::error::RustifiedError::generic(format!("Confirmation failed: {e}")))
+}
+
+/// Runs the `prepare` command, printing the prepared version last.
+///
+/// # Errors
+///
+/// Returns an error if selecting, preparing or rebuilding the natives of the version fails.
+pub async fn run_prepare(
+    launcher: &launcher::Launcher,
+    options: PrepareArgs,
+) -> crate::error::Result<()> {
+    let version = match (options.latest, options.latest_snapshot) {
+        (true, _) => Some("latest-release".to_string()),
+        (_, true) => Some("latest-snapshot".to_string()),
+        _ => options.version,
+    };
+    let version = select_prepare_version(launcher, version, options.interactive).await?;
+    let resolved = if options.rebuild_natives {
+        rebuild_natives(launcher, &version, &options).await?
+    } else {
+        prepare_game(launcher, &version, &options).await?
+    };
+
+    // The only stdout output (logs go to stderr), so scripts can capture the concrete version
+    println!("{resolved}");
     Ok(())
 }
 
+/// Re-extracts a prepared version's natives from the library jars already on disk.
+///
+/// Nothing is downloaded; missing jars are reported instead.
+async fn rebuild_natives(
+    launcher: &launcher::Launcher,
+    version: &str,
+    options: &PrepareArgs,
+) -> crate::error::Result<String> {
+    let resolved_version = resolve_version_alias(launcher, version).await?;
+    let minecraft_dir = target_minecraft_dir(launcher, options)?;
+
+    let version_info =
+        launcher::FileManager::get_local_version_info(&resolved_version, &minecraft_dir).await?;
+    launcher
+        .file_manager
+        .ensure_natives(&version_info, &minecraft_dir, true)
+        .await?;
+    Ok(resolved_version)
+}
+
+/// The directory to prepare into: `output_dir` when given, else the Minecraft directory
+fn target_minecraft_dir(
+    launcher: &launcher::Launcher,
+    options: &PrepareArgs,
+) -> crate::error::Result<launcher::MinecraftDir> {
+    let Some(path) = options.output_dir.as_deref() else {
+        return Ok(launcher.minecraft_dir.clone());
+    };
+    let dir = launcher::MinecraftDir::at(path)?;
+
+    let official = launcher::MinecraftDir::detect_official();
+    let link = options.link_official || launcher.config.link_official;
+    match official {
+        Some(official) if link => {
+            info!(
+                "Using libraries and assets from the official launcher in {}",
+                official.display()
+            );
+            Ok(dir.sharing_files_from(&official))
+        }
+        Some(official) => {
+            info!(
+                "💡 Found the official launcher in {}; pass --link-official to reuse its libraries and assets",
+                official.display()
+            );
+            Ok(dir)
+        }
+        None => {
+            if link {
+                warn!("⚠ No official launcher installation found, downloading everything");
+            }
+            Ok(dir)
+        }
+    }
+}
+
 /// Prepares the specified Minecraft version by downloading necessary files and assets.
 ///
+/// Files go into `--output-dir` (laid out like `.minecraft`) when given, otherwise into the
+/// launcher's Minecraft directory. The download size is estimated first; with `--estimate`
+/// nothing is downloaded. Returns the concrete version, with any alias resolved.
+///
 /// # Errors
 ///
 /// Returns an error if resolving the version alias, fetching version info, creating directories,
-/// or downloading game files, libraries, or assets fails.
+/// or downloading game files, libraries, or assets fails, or a large download is declined.
 pub async fn prepare_game(
     launcher: &launcher::Launcher,
     version: &str,
-) -> crate::error::Result<()> {
+    options: &PrepareArgs,
+) -> crate::error::Result<String> {
     // ...existing code from main.rs...
     let resolved_version = super::game::resolve_version_alias(launcher, version).await?;
 
-    info!("Preparing Minecraft {resolved_version} (no authentication required)...");
+    let minecraft_dir = target_minecraft_dir(launcher, options)?;
+    info!(
+        "Preparing Minecraft {resolved_version} in {} (no authentication required)...",
+        minecraft_dir.base_path.display()
+    );
+
+    // Files shared with an already-prepared version are trusted instead of re-hashed, except
+    // the official launcher's, which are always verified
+    let known = match options.since_version.as_deref() {
+        Some(_) if minecraft_dir.shares_files() => {
+            warn!(
+                "⚠ Ignoring --since-version: files shared with the official launcher are always verified"
+            );
+            std::collections::HashSet::new()
+        }
+        Some(previous) => {
+            let hashes = launcher::FileManager::prepared_file_hashes(previous, &minecraft_dir)
+                .await
+                .with_context(|| format!("Cannot prepare incrementally from {previous}"))?;
+            info!(
+                "Reusing {} libraries and assets already prepared for {previous}",
+                hashes.len()
+            );
+            hashes
+        }
+        None => std::collections::HashSet::new(),
+    };
 
     // Get version info and download files without authentication
     let version_info = launcher
@@ -160,35 +704,54 @@ pub async fn prepare_game(
         .get_version_info(&resolved_version)
         .await?;
 
+    let pending = launcher::FileManager::pending_download_size(&version_info, &minecraft_dir).await;
+    info!("Download size: about {}", super::format_size(pending));
+    if options.estimate {
+        return Ok(resolved_version);
+    }
+    if pending >= LARGE_DOWNLOAD && !options.yes && !confirm_download(pending)? {
+        return Err(crate::error::GameError::preparation_failed("Download cancelled").into());
+    }
+
     // Ensure version directory exists
-    launcher
-        .minecraft_dir
-        .ensure_version_dir(&resolved_version)?;
+    minecraft_dir.ensure_version_dir(&resolved_version)?;
 
     // Download main game JAR
+    let file_manager = &launcher.file_manager;
     launcher
-        .file_manager
-        .download_game_jar(&version_info, &launcher.minecraft_dir)
+        .timings
+        .time(
+            "client jar",
+            file_manager.download_game_jar(&version_info, &minecraft_dir),
+        )
         .await?;
 
     // Download libraries
     launcher
-        .file_manager
-        .download_libraries(&version_info, &launcher.minecraft_dir)
+        .timings
+        .time(
+            "libraries",
+            file_manager.download_libraries(&version_info, &minecraft_dir, &known),
+        )
         .await?;
 
     // Download assets
     launcher
-        .file_manager
-        .download_assets(&version_info, &launcher.minecraft_dir)
+        .timings
+        .time(
+            "assets",
+            file_manager.download_assets(&version_info, &minecraft_dir, &known),
+        )
         .await?;
 
     info!("✓ Minecraft {resolved_version} prepared successfully");
-    Ok(())
+    Ok(resolved_version)
 }
 
 /// Launches the specified Minecraft instance, handling authentication and preparation.
 ///
+/// Returns the game's exit code (`0` for a dry run or an emitted command).
+///
 /// # Errors
 ///
 /// Returns an error if the instance does not exist, the Minecraft version is invalid,
@@ -196,8 +759,8 @@ pub async fn prepare_game(
 pub async fn launch_game(
     launcher: &launcher::Launcher,
     instance_name: &str,
-    _skip_verification: bool,
-) -> crate::error::Result<()> {
+    options: &LaunchArgs,
+) -> crate::error::Result<i32> {
     let (instance_config, version) = {
         let instance_manager = launcher.instance_manager.lock().await;
         if let Some(config) = instance_manager.get_instance(instance_name) {
@@ -211,22 +774,32 @@ pub async fn launch_game(
         }
     };
 
-    let resolved_version = super::game::resolve_version_alias(launcher, &version).await?;
+    let settings = launch_settings(&launcher.config, options, instance_config.as_ref())?;
+
+    let resolved_version = if options.offline {
+        check_alias_typo(&version)?;
+        if is_version_alias(&version) {
+            return Err(crate::error::GameError::invalid_version(format!(
+                "Version alias '{version}' cannot be resolved offline. Set the instance to a concrete version."
+            ))
+            .into());
+        }
+        version
+    } else {
+        super::game::resolve_version_alias(launcher, &version).await?
+    };
 
     // Validate Minecraft version before authentication
-    if let Err(e) = launcher
-        .file_manager
-        .get_version_info(&resolved_version)
-        .await
-    {
-        error!("Invalid Minecraft version: {resolved_version} : {e}");
-        return Err(crate::error::GameError::invalid_version(format!(
-            "Instance '{instance_name}' uses an invalid Minecraft version ('{resolved_version}'). Use 'Redstonium list' to see valid versions."
-        )).into());
+    let mut version_info =
+        load_launch_version_info(launcher, instance_name, &resolved_version, options.offline)
+            .await?;
+    if let Some(config) = &instance_config {
+        version_info = launcher::apply_loader(version_info, &config.mods, &launcher.minecraft_dir)?;
     }
 
     // Update last used timestamp
-    {
+    let starts_game = settings.starts_game();
+    if starts_game {
         let mut instance_manager = launcher.instance_manager.lock().await;
         instance_manager.update_last_used(instance_name).await?;
     }
@@ -234,34 +807,230 @@ pub async fn launch_game(
     info!("Launching Minecraft {resolved_version} with instance '{instance_name}'...");
 
     // Authenticate first
+    let auth_result = authenticate_for_launch(launcher, options).await?;
+
+    ensure_game_files(launcher, &version_info, options).await?;
+    launcher
+        .file_manager
+        .ensure_natives(&version_info, &launcher.minecraft_dir, false)
+        .await?;
+
+    // Launch the game
+    info!("Starting Minecraft {resolved_version}...");
+
+    let exit_code = launcher
+        .launch_game(
+            &version_info,
+            &auth_result,
+            instance_config.as_ref(),
+            &settings,
+        )
+        .await?;
+    if !starts_game {
+        return Ok(exit_code);
+    }
+
+    if exit_code == 0 {
+        info!("✓ Minecraft exited");
+    } else {
+        error!("❌ Minecraft exited with code {exit_code}");
+        info!("💡 Check the instance's logs and crash-reports directories for details");
+    }
+
+    if options.keep_open || launcher.config.keep_open {
+        wait_for_keypress();
+    }
+
+    Ok(exit_code)
+}
+
+/// Pause until a key is pressed, so a console window opened just for the launcher stays up
+///
+/// Does nothing when not attached to a terminal.
+fn wait_for_keypress() {
+    if !(std::io::stdin().is_terminal() && std::io::stderr().is_terminal()) {
+        return;
+    }
+
+    let term = dialoguer::console::Term::stderr();
+    if term.write_line("Press any key to close...").is_ok() {
+        let _ = term.read_key();
+    }
+}
+
+/// Makes sure the game files are present and intact before launching.
+///
+/// Online launches prepare the game, which re-downloads anything missing or corrupt.
+/// Offline launches can't repair files, so they re-hash the client JAR and libraries
+/// and fail with a clear message instead of letting the JVM crash on a truncated JAR.
+async fn ensure_game_files(
+    launcher: &launcher::Launcher,
+    version_info: &launcher::VersionInfo,
+    options: &LaunchArgs,
+) -> crate::error::Result<()> {
+    let version = &version_info.id;
+    let installed = launcher.minecraft_dir.is_version_installed(version);
+
+    if options.skip_verification && installed {
+        info!("Skipping file verification");
+        return Ok(());
+    }
+
+    if !options.offline {
+        // Prepare the game (download if necessary)
+        info!("Preparing game files...");
+        launcher.prepare_game(version_info).await?;
+        info!("✓ Game files prepared successfully");
+        return Ok(());
+    }
+
+    if !installed {
+        return Err(crate::error::GameError::preparation_failed(format!(
+            "Minecraft {version} is not fully prepared. Run 'Redstonium prepare {version}' while online first."
+        ))
+        .into());
+    }
+
+    info!("Offline mode: verifying game files...");
+    let invalid = launcher
+        .file_manager
+        .find_invalid_game_files(version_info, &launcher.minecraft_dir)
+        .await?;
+    if !invalid.is_empty() {
+        for name in &invalid {
+            error!("❌ Missing or corrupt: {name}");
+        }
+        return Err(crate::error::GameError::preparation_failed(format!(
+            "{} game files are missing or corrupt. Launch without --offline or run 'Redstonium prepare {version}' to repair them.",
+            invalid.len()
+        ))
+        .into());
+    }
+    info!("✓ Game files verified");
+    Ok(())
+}
+
+/// Builds the per-launch game settings from the CLI options and instance config
+fn launch_settings(
+    config: &launcher::LauncherConfig,
+    options: &LaunchArgs,
+    instance_config: Option<&launcher::InstanceConfig>,
+) -> crate::error::Result<launcher::LaunchSettings> {
+    Ok(launcher::LaunchSettings {
+        brand: config.brand().to_string(),
+        dry_run: options.dry_run,
+        emit_command: options.emit_command.clone(),
+        export_script: options.export_script.clone(),
+        // Joining a server replaces the instance's quick play world; the game refuses both
+        world: options.world.clone().or_else(|| {
+            options
+                .connect
+                .is_none()
+                .then(|| instance_config?.settings.quick_play_world.clone())
+                .flatten()
+        }),
+        demo: options
+            .demo_override()
+            .unwrap_or_else(|| instance_config.is_some_and(|config| config.settings.demo)),
+        server: options
+            .connect
+            .as_deref()
+            .map(launcher::ServerAddress::parse)
+            .transpose()?,
+        backup_saves: options.backup
+            || instance_config.is_some_and(|config| config.settings.backup_before_launch),
+        launch_timeout: options.launch_timeout.map(std::time::Duration::from_secs),
+        strict_java: options.strict_java
+            || instance_config.is_some_and(|config| config.settings.strict_java),
+        force: options.force,
+    })
+}
+
+/// Loads the version info for a launch, from disk only when offline
+async fn load_launch_version_info(
+    launcher: &launcher::Launcher,
+    instance_name: &str,
+    version: &str,
+    offline: bool,
+) -> crate::error::Result<launcher::VersionInfo> {
+    if offline {
+        return launcher::FileManager::get_local_version_info(version, &launcher.minecraft_dir)
+            .await;
+    }
+
+    match launcher
+        .file_manager
+        .get_version_info_cached(version, &launcher.minecraft_dir)
+        .await
+    {
+        Ok(version_info) => Ok(version_info),
+        Err(e) => {
+            error!("Invalid Minecraft version: {version} : {e}");
+            Err(crate::error::GameError::invalid_version(format!(
+                "Instance '{instance_name}' uses an invalid Minecraft version ('{version}'). Use 'Redstonium list' to see valid versions."
+            )).into())
+        }
+    }
+}
+
+/// Authenticates for a launch; offline launches may only use the cached login
+///
+/// With `--refresh-profile`, the profile is fetched again; if that fails the cached one is kept.
+async fn authenticate_for_launch(
+    launcher: &launcher::Launcher,
+    options: &LaunchArgs,
+) -> crate::error::Result<crate::auth::AuthResult> {
     info!("Starting authentication process...");
-    let auth_result = match crate::auth::authenticate().await {
-        Ok(result) => {
+    let result: crate::error::Result<_> = if options.offline {
+        crate::auth::storage::AuthStorage::new()?
+            .load_auth()
+            .await?
+            .ok_or_else(|| {
+                crate::error::AuthError::cache_error(
+                    "Offline launch requires a cached login. Launch once while online to sign in.",
+                )
+                .into()
+            })
+    } else {
+        crate::auth::authenticate(launcher.config.auth_refresh_threshold()).await
+    };
+
+    match result {
+        Ok(mut result) => {
+            // The profile is cached with the login, so a name change is missed otherwise
+            if options.refresh_profile
+                && let Err(e) = crate::auth::refresh_profile(&mut result).await
+            {
+                warn!("⚠ {e}; using the cached profile");
+            }
             info!("Authentication successful!");
             info!("Welcome, {}!", result.profile.name);
-            result
+            Ok(result)
         }
         Err(e) => {
             error!("Authentication failed: {e}");
-            return Err(e);
+            Err(e)
         }
-    };
-
-    // Prepare the game (download if necessary)
-    info!("Preparing game files...");
-    launcher
-        .prepare_game(&resolved_version, &auth_result)
-        .await?;
-    info!("✓ Game files prepared successfully");
+    }
+}
 
-    // Launch the game
-    info!("Starting Minecraft {resolved_version}...");
+/// Whether the version string is one of the `latest` aliases
+fn is_version_alias(version: &str) -> bool {
+    VERSION_ALIASES.contains(&version)
+}
 
-    launcher
-        .launch_game(&resolved_version, &auth_result, instance_config.as_ref())
-        .await?;
-    info!("✓ Minecraft exited");
+/// Version aliases resolved against the manifest
+const VERSION_ALIASES: [&str; 3] = ["latest", "latest-release", "latest-snapshot"];
 
+/// Rejects strings that look like an alias but aren't one (e.g. `latest-relase`)
+fn check_alias_typo(version: &str) -> crate::error::Result<()> {
+    if version.starts_with("latest-") && !is_version_alias(version) {
+        return Err(crate::error::GameError::invalid_version(format!(
+            "Unknown version alias '{version}'. Valid aliases are: {}",
+            VERSION_ALIASES.join(", ")
+        ))
+        .into());
+    }
     Ok(())
 }
 
@@ -269,20 +1038,163 @@ pub async fn launch_game(
 ///
 /// # Errors
 ///
-/// Returns an error if fetching the version manifest fails.
+/// Returns an error if the version looks like a misspelled alias or fetching the
+/// version manifest fails.
 pub async fn resolve_version_alias(
     launcher: &launcher::Launcher,
     version: &str,
 ) -> crate::error::Result<String> {
+    check_alias_typo(version)?;
+
+    if !is_version_alias(version) {
+        return Ok(version.to_string());
+    }
+
+    let manifest = launcher.file_manager.get_version_manifest().await?;
+    Ok(resolve_alias_in_manifest(manifest, version))
+}
+
+/// Resolves a version alias against an already fetched manifest; other strings pass through
+pub fn resolve_alias_in_manifest(manifest: &launcher::VersionManifest, version: &str) -> String {
     match version {
-        "latest-release" | "latest" => {
-            let manifest = launcher.file_manager.get_version_manifest().await?;
-            Ok(manifest.latest.release)
+        "latest-release" | "latest" => manifest.latest.release.clone(),
+        "latest-snapshot" => manifest.latest.snapshot.clone(),
+        _ => version.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn entry(id: &str, release_time: &str) -> launcher::VersionEntry {
+        launcher::VersionEntry {
+            id: id.to_string(),
+            version_type: launcher::VersionType::Release,
+            url: String::new(),
+            time: release_time.to_string(),
+            release_time: release_time.to_string(),
+            sha1: None,
         }
-        "latest-snapshot" => {
-            let manifest = launcher.file_manager.get_version_manifest().await?;
-            Ok(manifest.latest.snapshot)
+    }
+
+    fn ids(versions: &[launcher::VersionEntry]) -> Vec<&str> {
+        versions.iter().map(|v| v.id.as_str()).collect()
+    }
+
+    /// Launch options parsed from `launch` command-line arguments
+    fn launch_args(args: &[&str]) -> LaunchArgs {
+        let cli =
+            crate::cli::Cli::try_parse_from(["Redstonium", "launch", "test"].iter().chain(args))
+                .unwrap();
+        match cli.command {
+            crate::cli::Commands::Launch { options, .. } => Some(options),
+            _ => None,
         }
-        _ => Ok(version.to_string()),
+        .unwrap()
+    }
+
+    /// An instance with the given `settings` in `instance.json`
+    fn instance_with(settings: serde_json::Value) -> launcher::InstanceConfig {
+        let mut base = serde_json::json!({"java_args": [], "game_args": [], "debug": false});
+        base.as_object_mut()
+            .unwrap()
+            .extend(settings.as_object().unwrap().clone());
+        serde_json::from_value(serde_json::json!({
+            "name": "test",
+            "version": "1.21",
+            "created": "2024-01-01T00:00:00Z",
+            "settings": base,
+            "mods": {"loader": "vanilla", "mods": []}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_launch_settings_connect_replaces_instance_world() {
+        let config = launcher::LauncherConfig::default();
+        let instance = instance_with(serde_json::json!({"quick_play_world": "New World"}));
+
+        let settings = launch_settings(&config, &launch_args(&[]), Some(&instance)).unwrap();
+        assert_eq!(settings.world.as_deref(), Some("New World"));
+
+        let options = launch_args(&["--connect", "play.example.com"]);
+        let settings = launch_settings(&config, &options, Some(&instance)).unwrap();
+        assert_eq!(settings.world, None);
+        assert!(settings.server.is_some());
+    }
+
+    #[test]
+    fn test_launch_settings_demo_flags_override_instance() {
+        let config = launcher::LauncherConfig::default();
+        let instance = instance_with(serde_json::json!({"demo": true}));
+
+        let settings = launch_settings(&config, &launch_args(&[]), Some(&instance)).unwrap();
+        assert!(settings.demo);
+
+        let options = launch_args(&["--no-demo"]);
+        let settings = launch_settings(&config, &options, Some(&instance)).unwrap();
+        assert!(!settings.demo);
+
+        let options = launch_args(&["--no-demo", "--demo"]);
+        let settings = launch_settings(&config, &options, None).unwrap();
+        assert!(settings.demo);
+    }
+
+    #[test]
+    fn test_sort_by_release_date() {
+        let mut versions = vec![
+            entry("b1.8", "2011-09-14T22:00:00+00:00"),
+            entry("broken", "not a date"),
+            entry("1.20", "2023-06-02T08:36:17+00:00"),
+            entry("a1.0.4", "2010-07-08T22:00:00+00:00"),
+        ];
+
+        sort_by_release_date(&mut versions, true);
+        assert_eq!(ids(&versions), ["1.20", "b1.8", "a1.0.4", "broken"]);
+
+        sort_by_release_date(&mut versions, false);
+        assert_eq!(ids(&versions), ["a1.0.4", "b1.8", "1.20", "broken"]);
+    }
+
+    #[test]
+    fn test_retain_latest_per_minor() {
+        let mut versions = vec![
+            entry("1.20.6", "2024-04-29T12:00:00+00:00"),
+            entry("1.20.5", "2024-04-23T12:00:00+00:00"),
+            entry("24w14a", "2024-04-03T12:00:00+00:00"),
+            entry("1.20", "2023-06-02T08:36:17+00:00"),
+            entry("1.19.4", "2023-03-14T12:00:00+00:00"),
+            entry("1.19.4-rc1", "2023-03-09T12:00:00+00:00"),
+        ];
+
+        retain_latest_per_minor(&mut versions);
+        assert_eq!(ids(&versions), ["1.20.6", "24w14a", "1.19.4"]);
+    }
+
+    #[test]
+    fn test_fuzzy_match_versions() {
+        let versions = vec![
+            entry("1.20.1", "2023-06-12T13:25:51+00:00"),
+            entry("1.19.2", "2022-08-05T11:57:05+00:00"),
+            entry("1.19", "2022-06-07T09:42:18+00:00"),
+            entry("1.8.9", "2015-12-03T09:24:39+00:00"),
+        ];
+
+        let found: Vec<&str> = fuzzy_match_versions(&versions, "119")
+            .iter()
+            .map(|v| v.id.as_str())
+            .collect();
+        assert!(found.contains(&"1.19.2") && found.contains(&"1.19"));
+        assert!(!found.contains(&"1.8.9"));
+    }
+
+    #[test]
+    fn test_check_alias_typo() {
+        assert!(check_alias_typo("latest").is_ok());
+        assert!(check_alias_typo("latest-snapshot").is_ok());
+        assert!(check_alias_typo("1.21.4").is_ok());
+        assert!(check_alias_typo("latest-relase").is_err());
     }
 }
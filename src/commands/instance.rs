@@ -1,93 +1,62 @@
-use crate::cli::InstanceCommands;
-use tracing::{error, info};
+use crate::cli::{InstanceCommands, InstanceSort, LoaderCommands, LoaderKind};
+use crate::error::ResultExt;
+use crate::launcher::{Checksum, InstanceConfig, Launcher, ModLoader};
+use chrono::{DateTime, Local, Utc};
+use std::cmp::Ordering;
+use tracing::{error, info, warn};
 
 /// Handles all instance-related commands.
 ///
 /// # Errors
-/// Returns an error if instance operations (list, create, delete, info, memory) fail.
+/// Returns an error if instance operations (list, create, delete, info, memory, env, wrapper,
+/// backup, restore, loader, prune, open, logs) fail.
 pub async fn handle_instance_command(
-    launcher: &crate::launcher::Launcher,
+    launcher: &Launcher,
     action: InstanceCommands,
 ) -> crate::error::Result<()> {
     match action {
-        InstanceCommands::List => {
-            let instance_manager = launcher.instance_manager.lock().await;
-            let instances: Vec<_> = instance_manager
-                .list_instances()
-                .into_iter()
-                .cloned()
-                .collect();
-            drop(instance_manager); // Release lock early
-
-            if instances.is_empty() {
-                info!(
-                    "No instances found. Create one with: Redstonium instance create <name> <version>"
-                );
-            } else {
-                info!("Available instances:");
-                for instance in instances {
-                    let last_used = if let Some(used) = instance.last_used {
-                        format!(" (last used: {})", used.format("%Y-%m-%d %H:%M:%S"))
-                    } else {
-                        String::new()
-                    };
-
-                    let description = instance
-                        .description
-                        .as_ref()
-                        .map(|d| format!(" - {d}"))
-                        .unwrap_or_default();
-
-                    info!(
-                        "  {} (v{}){}{}",
-                        instance.name, instance.version, description, last_used
-                    );
-                }
-            }
+        InstanceCommands::List { sort, time_format } => {
+            list_instances(launcher, sort, time_format.as_deref()).await;
         }
-        InstanceCommands::Info { name } => {
-            let instance_manager = launcher.instance_manager.lock().await;
-            if let Some(instance) = instance_manager.get_instance(&name) {
-                let instance = instance.clone(); // Clone to avoid borrow issues
-                drop(instance_manager); // Release lock
-
-                info!("Instance: {}", instance.name);
-                info!("  Version: {}", instance.version);
-                if let Some(desc) = &instance.description {
-                    info!("  Description: {desc}");
-                }
-                info!(
-                    "  Created: {}",
-                    instance.created.format("%Y-%m-%d %H:%M:%S")
-                );
-                if let Some(used) = instance.last_used {
-                    info!("  Last used: {}", used.format("%Y-%m-%d %H:%M:%S"));
-                }
-                info!("  Mod loader: {:?}", instance.mods.loader);
-                if let Some(memory) = instance.settings.memory_mb {
-                    info!("  Memory: {memory}MB");
-                }
-                if !instance.settings.java_args.is_empty() {
-                    info!("  Java args: {}", instance.settings.java_args.join(" "));
-                }
-            } else {
-                error!("Instance '{name}' does not exist");
-                return Err(crate::error::InstanceError::not_found(
-                    "Instance not found".to_string(),
-                )
-                .into());
-            }
+        InstanceCommands::Info { name, time_format } => {
+            let time_format = time_format.as_deref().unwrap_or(DEFAULT_TIME_FORMAT);
+            show_instance_info(launcher, &name, time_format).await?;
         }
         InstanceCommands::Create {
             name,
             version,
             description,
+            from,
+            checksum,
+            force,
+            keep_saves,
         } => {
-            let mut instance_manager = launcher.instance_manager.lock().await;
-            instance_manager
-                .create_instance(name.clone(), version, description, &launcher.file_manager)
-                .await?;
-            info!("✓ Created instance '{name}'");
+            let existing = if force {
+                OnExisting::Replace { keep_saves }
+            } else {
+                OnExisting::Fail
+            };
+            match (version, from) {
+                (_, Some(pack)) => {
+                    let pack = PackSource {
+                        location: &pack,
+                        checksum: checksum.as_deref(),
+                    };
+                    create_instance_from_pack(launcher, name, pack, description, existing).await?;
+                }
+                (version, None) => {
+                    let version = version.ok_or_else(|| {
+                        crate::error::InstanceError::invalid_config(
+                            "Specify a Minecraft version or --from <pack>",
+                        )
+                    })?;
+                    create_instance(launcher, name, version, description, existing).await?;
+                }
+            }
+        }
+        InstanceCommands::Open { name } => open_instance_dir(launcher, &name).await?,
+        InstanceCommands::Logs { name, follow } => {
+            show_instance_log(launcher, &name, follow).await?;
         }
         InstanceCommands::Delete { name } => {
             let mut instance_manager = launcher.instance_manager.lock().await;
@@ -99,6 +68,759 @@ pub async fn handle_instance_command(
             instance_manager.set_instance_memory(&name, memory).await?;
             info!("✓ Set memory for instance '{name}' to {memory}MB");
         }
+        InstanceCommands::Env { name, vars } => set_instance_env(launcher, &name, &vars).await?,
+        InstanceCommands::Wrapper {
+            name,
+            command,
+            clear,
+        } => {
+            let wrapper = (!clear).then(|| command.clone());
+            let mut instance_manager = launcher.instance_manager.lock().await;
+            instance_manager
+                .set_instance_wrapper(&name, wrapper)
+                .await?;
+            if clear {
+                info!("✓ Cleared wrapper for instance '{name}'");
+            } else {
+                info!(
+                    "✓ Instance '{name}' will launch through: {}",
+                    command.join(" ")
+                );
+            }
+        }
+        InstanceCommands::Backup { name, output } => {
+            let output = output.unwrap_or_else(|| {
+                let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+                std::path::PathBuf::from(format!("{name}-{timestamp}.zip"))
+            });
+            let instance_manager = launcher.instance_manager.lock().await;
+            let count = instance_manager.backup_instance(&name, &output)?;
+            info!(
+                "✓ Backed up instance '{name}' ({count} files) to {}",
+                output.display()
+            );
+        }
+        InstanceCommands::Restore { file, name } => {
+            let mut instance_manager = launcher.instance_manager.lock().await;
+            let name = instance_manager.restore_instance(&file, name).await?;
+            info!("✓ Restored instance '{name}' from {}", file.display());
+        }
+        InstanceCommands::Loader { action } => handle_loader_command(launcher, action).await?,
+        InstanceCommands::Prune {
+            older_than,
+            dry_run,
+        } => prune_instances(launcher, older_than, dry_run).await?,
+    }
+    Ok(())
+}
+
+/// Timestamp format when none is given
+const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Formats a stored UTC timestamp in local time
+fn format_timestamp(time: DateTime<Utc>, format: &str) -> String {
+    time.with_timezone(&Local).format(format).to_string()
+}
+
+/// Describes how long before `now` a time was, e.g. "2 hours ago"
+fn format_relative(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(time);
+    let (count, unit) = match elapsed.num_seconds() {
+        ..60 => return "just now".to_string(),
+        ..3600 => (elapsed.num_minutes(), "minute"),
+        ..86_400 => (elapsed.num_hours(), "hour"),
+        _ => match elapsed.num_days() {
+            ..30 => (elapsed.num_days(), "day"),
+            ..365 => (elapsed.num_days() / 30, "month"),
+            days => (days / 365, "year"),
+        },
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{count} {unit}{plural} ago")
+}
+
+/// Prints all instances with their version and last-used time
+///
+/// Last use is shown relative to now unless a `time_format` is given.
+async fn list_instances(launcher: &Launcher, sort: InstanceSort, time_format: Option<&str>) {
+    let instance_manager = launcher.instance_manager.lock().await;
+    let mut instances: Vec<_> = instance_manager
+        .list_instances()
+        .into_iter()
+        .cloned()
+        .collect();
+    drop(instance_manager); // Release lock early
+
+    sort_instances(&mut instances, sort);
+
+    if instances.is_empty() {
+        info!("No instances found. Create one with: Redstonium instance create <name> <version>");
+    } else {
+        info!("Available instances:");
+        let now = Utc::now();
+        for instance in instances {
+            let last_used = match (instance.last_used, time_format) {
+                (Some(used), Some(format)) => {
+                    format!(" (last used: {})", format_timestamp(used, format))
+                }
+                (Some(used), None) => format!(" (last used {})", format_relative(used, now)),
+                (None, _) => String::new(),
+            };
+
+            let description = instance
+                .description
+                .as_ref()
+                .map(|d| format!(" - {d}"))
+                .unwrap_or_default();
+
+            info!(
+                "  {} (v{}){}{}",
+                instance.name, instance.version, description, last_used
+            );
+        }
+    }
+}
+
+/// A modpack to create an instance from: a file path or URL, with an optional checksum
+struct PackSource<'a> {
+    location: &'a str,
+    checksum: Option<&'a str>,
+}
+
+/// Creates an instance for the Minecraft version and loader a modpack targets
+///
+/// The pack's mods are not downloaded.
+async fn create_instance_from_pack(
+    launcher: &Launcher,
+    name: String,
+    pack: PackSource<'_>,
+    description: Option<String>,
+    existing: OnExisting,
+) -> crate::error::Result<()> {
+    let PackSource {
+        location: source,
+        checksum,
+    } = pack;
+    let checksum = checksum.map(Checksum::parse).transpose()?;
+    let pack = if source.starts_with("http://") || source.starts_with("https://") {
+        // Keep a bare manifest recognisable; anything else is read as a zip
+        let extension = if source.ends_with(".json") {
+            "json"
+        } else {
+            "zip"
+        };
+        let download = std::env::temp_dir().join(format!(
+            "redstonium-pack-{}.{extension}",
+            std::process::id()
+        ));
+        launcher
+            .file_manager
+            .download_to(source, &download, checksum)
+            .await?;
+        let pack = crate::launcher::read_pack(&download);
+        let _ = std::fs::remove_file(&download);
+        pack?
+    } else {
+        let path = std::path::Path::new(source);
+        if let Some(checksum) = checksum {
+            let content = std::fs::read(path)
+                .with_context(|| format!("Failed to read pack: {}", path.display()))?;
+            checksum.verify(&content)?;
+        }
+        crate::launcher::read_pack(path)?
+    };
+
+    info!(
+        "Pack targets Minecraft {} with {}{}",
+        pack.minecraft_version,
+        pack.loader,
+        pack.loader_version
+            .as_deref()
+            .map(|version| format!(" {version}"))
+            .unwrap_or_default()
+    );
+
+    let description = description.or(pack.name);
+    create_instance(
+        launcher,
+        name.clone(),
+        pack.minecraft_version,
+        description,
+        existing,
+    )
+    .await?;
+
+    if !matches!(pack.loader, ModLoader::Vanilla) {
+        let mut instance_manager = launcher.instance_manager.lock().await;
+        instance_manager
+            .set_instance_loader(&name, pack.loader, pack.loader_version)
+            .await?;
+        info!("💡 The pack's mods are not downloaded; add them to the instance's mods folder");
+    }
+    Ok(())
+}
+
+/// Installs or manages an instance's mod loader
+async fn handle_loader_command(
+    launcher: &Launcher,
+    action: LoaderCommands,
+) -> crate::error::Result<()> {
+    match action {
+        LoaderCommands::Quilt {
+            name,
+            loader_version,
+        } => {
+            let version = instance_version(launcher, &name).await?;
+            let version = super::game::resolve_version_alias(launcher, &version).await?;
+            let installed = crate::launcher::install_quilt(
+                &launcher.file_manager,
+                &launcher.minecraft_dir,
+                &version,
+                loader_version.as_deref(),
+            )
+            .await?;
+
+            let mut instance_manager = launcher.instance_manager.lock().await;
+            instance_manager
+                .set_instance_loader(&name, ModLoader::Quilt, Some(installed.clone()))
+                .await?;
+            info!("✓ Instance '{name}' now launches with Quilt {installed}");
+        }
+        LoaderCommands::Remove { name, disable_mods } => {
+            remove_loader(launcher, &name, disable_mods).await?;
+        }
+        LoaderCommands::Versions {
+            loader,
+            mc_version,
+            limit,
+        } => list_loader_versions(launcher, loader, mc_version.as_deref(), limit).await?,
+    }
+    Ok(())
+}
+
+/// Reverts an instance to vanilla, deleting its loader profile unless another instance uses it
+async fn remove_loader(
+    launcher: &Launcher,
+    name: &str,
+    disable_mods: bool,
+) -> crate::error::Result<()> {
+    let (instance, shared, instance_dir) = {
+        let mut instance_manager = launcher.instance_manager.lock().await;
+        let Some(instance) = instance_manager.get_instance(name).cloned() else {
+            return Err(crate::error::InstanceError::not_found(format!(
+                "Instance '{name}' does not exist"
+            ))
+            .into());
+        };
+        if matches!(instance.mods.loader, ModLoader::Vanilla) {
+            info!("Instance '{name}' already launches vanilla Minecraft");
+            return Ok(());
+        }
+        let shared = instance_manager.list_instances().iter().any(|other| {
+            other.name != name
+                && other.version == instance.version
+                && other.mods.loader == instance.mods.loader
+                && other.mods.loader_version == instance.mods.loader_version
+        });
+        instance_manager
+            .set_instance_loader(name, ModLoader::Vanilla, None)
+            .await?;
+        (instance, shared, instance_manager.get_instance_dir(name))
+    };
+    info!("✓ Removed {} from instance '{name}'", instance.mods.loader);
+
+    if let Some(loader_version) = &instance.mods.loader_version {
+        if shared {
+            info!("Keeping the loader profile; other instances still use it");
+        } else {
+            let version = super::game::resolve_version_alias(launcher, &instance.version).await?;
+            if crate::launcher::remove_profile(
+                &launcher.minecraft_dir,
+                &instance.mods.loader,
+                loader_version,
+                &version,
+            )? {
+                info!(
+                    "✓ Deleted the {} {loader_version} loader profile",
+                    instance.mods.loader
+                );
+            }
+        }
+    }
+
+    if disable_mods {
+        let mods_dir = instance_dir.join("mods");
+        let disabled_dir = instance_dir.join("mods.disabled");
+        if !mods_dir.exists() {
+            info!("No mods folder to disable");
+        } else if disabled_dir.exists() {
+            warn!(
+                "⚠ {} already exists; leaving the mods folder in place",
+                disabled_dir.display()
+            );
+        } else {
+            std::fs::rename(&mods_dir, &disabled_dir).with_context(|| {
+                format!("Failed to move mods folder to {}", disabled_dir.display())
+            })?;
+            info!("✓ Moved mods to {}", disabled_dir.display());
+        }
+    }
+    Ok(())
+}
+
+/// Prints a loader's newest releases, marking the recommended one
+async fn list_loader_versions(
+    launcher: &Launcher,
+    loader: LoaderKind,
+    mc_version: Option<&str>,
+    limit: usize,
+) -> crate::error::Result<()> {
+    let loader = match loader {
+        LoaderKind::Fabric => ModLoader::Fabric,
+        LoaderKind::Forge => ModLoader::Forge,
+        LoaderKind::Quilt => ModLoader::Quilt,
+    };
+    let versions =
+        crate::launcher::loader_versions(&launcher.file_manager, &loader, mc_version).await?;
+
+    let target = mc_version
+        .map(|v| format!(" for Minecraft {v}"))
+        .unwrap_or_default();
+    if versions.is_empty() {
+        info!("No {loader} versions found{target}");
+        return Ok(());
+    }
+
+    info!("Available {loader} versions{target} (newest first):");
+    for version in versions.iter().take(limit) {
+        let marker = if version.recommended {
+            " [recommended]"
+        } else if !version.stable {
+            " [beta]"
+        } else {
+            ""
+        };
+        info!("  {}{marker}", version.version);
+    }
+    if versions.len() > limit {
+        info!(
+            "  ... and {} more (use --limit to show more)",
+            versions.len() - limit
+        );
+    }
+    Ok(())
+}
+
+/// The Minecraft version an instance is set to
+async fn instance_version(launcher: &Launcher, name: &str) -> crate::error::Result<String> {
+    let instance_manager = launcher.instance_manager.lock().await;
+    instance_manager
+        .get_instance(name)
+        .map(|instance| instance.version.clone())
+        .ok_or_else(|| {
+            crate::error::InstanceError::not_found(format!("Instance '{name}' does not exist"))
+                .into()
+        })
+}
+
+/// Deletes instances not used within `older_than_days`, or only lists them with `dry_run`
+async fn prune_instances(
+    launcher: &Launcher,
+    older_than_days: u32,
+    dry_run: bool,
+) -> crate::error::Result<()> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(older_than_days));
+
+    let mut instance_manager = launcher.instance_manager.lock().await;
+    let mut stale: Vec<_> = instance_manager
+        .list_instances()
+        .into_iter()
+        .filter(|instance| instance.last_used.unwrap_or(instance.created) < cutoff)
+        .map(|instance| instance.name.clone())
+        .collect();
+    stale.sort();
+
+    if stale.is_empty() {
+        info!("No instances unused for more than {older_than_days} days");
+        return Ok(());
+    }
+
+    if dry_run {
+        info!("Would delete {} instance(s):", stale.len());
+        for name in &stale {
+            info!("  {name}");
+        }
+        return Ok(());
+    }
+
+    for name in &stale {
+        instance_manager.delete_instance(name).await?;
+        info!("✓ Deleted instance '{name}'");
+    }
+    info!("✓ Pruned {} instance(s)", stale.len());
+    Ok(())
+}
+
+/// Orders instances for display; ties fall back to the name so output is stable
+fn sort_instances(instances: &mut [InstanceConfig], sort: InstanceSort) {
+    instances.sort_by(|a, b| {
+        let primary = match sort {
+            InstanceSort::Name => Ordering::Equal,
+            InstanceSort::Created => b.created.cmp(&a.created),
+            // `None` sorts before `Some`, so reversing puts never-used instances last
+            InstanceSort::LastUsed => b.last_used.cmp(&a.last_used),
+            InstanceSort::Version => compare_versions(&b.version, &a.version),
+        };
+        primary.then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Compares version strings part by part, numerically where both parts are numbers
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a_parts: Vec<&str> = a.split(['.', '-', ' ']).collect();
+    let b_parts: Vec<&str> = b.split(['.', '-', ' ']).collect();
+
+    for (x, y) in a_parts.iter().zip(&b_parts) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a_parts.len().cmp(&b_parts.len())
+}
+
+/// Game directory of an instance, which must exist
+async fn existing_instance_dir(
+    launcher: &Launcher,
+    name: &str,
+) -> crate::error::Result<std::path::PathBuf> {
+    let instance_manager = launcher.instance_manager.lock().await;
+    if instance_manager.get_instance(name).is_none() {
+        return Err(crate::error::InstanceError::not_found(format!(
+            "Instance '{name}' does not exist"
+        ))
+        .into());
+    }
+    Ok(instance_manager.get_instance_dir(name))
+}
+
+/// Opens an instance's folder in the system file manager
+async fn open_instance_dir(launcher: &Launcher, name: &str) -> crate::error::Result<()> {
+    let instance_dir = existing_instance_dir(launcher, name).await?;
+
+    let opener = if cfg!(windows) {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    // Not waited on: explorer reports failure even when it opens the folder
+    std::process::Command::new(opener)
+        .arg(&instance_dir)
+        .spawn()
+        .with_context(|| format!("Failed to run {opener}"))?;
+    info!("✓ Opened {}", instance_dir.display());
+    Ok(())
+}
+
+/// Prints an instance's `logs/latest.log`, optionally following new output until interrupted
+async fn show_instance_log(
+    launcher: &Launcher,
+    name: &str,
+    follow: bool,
+) -> crate::error::Result<()> {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let log_path = existing_instance_dir(launcher, name)
+        .await?
+        .join("logs")
+        .join("latest.log");
+    if !log_path.exists() && !follow {
+        info!("Instance '{name}' has no log yet; launch it first");
+        return Ok(());
+    }
+
+    let mut position = 0;
+    loop {
+        if let Ok(mut file) = std::fs::File::open(&log_path) {
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            // The game starts a fresh latest.log on each launch
+            if len < position {
+                position = 0;
+            }
+            let mut new_output = Vec::new();
+            file.seek(SeekFrom::Start(position))
+                .and_then(|_| file.read_to_end(&mut new_output))
+                .with_context(|| format!("Failed to read {}", log_path.display()))?;
+            position += new_output.len() as u64;
+
+            let mut stdout = std::io::stdout().lock();
+            stdout
+                .write_all(&new_output)
+                .and_then(|()| stdout.flush())
+                .context("Failed to write log output")?;
+        }
+
+        if !follow {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+}
+
+/// Prints the details of a single instance
+async fn show_instance_info(
+    launcher: &Launcher,
+    name: &str,
+    time_format: &str,
+) -> crate::error::Result<()> {
+    let instance_manager = launcher.instance_manager.lock().await;
+    let Some(instance) = instance_manager.get_instance(name) else {
+        error!("Instance '{name}' does not exist");
+        return Err(
+            crate::error::InstanceError::not_found("Instance not found".to_string()).into(),
+        );
+    };
+    let instance = instance.clone(); // Clone to avoid borrow issues
+    drop(instance_manager); // Release lock
+
+    info!("Instance: {}", instance.name);
+    info!("  Version: {}", instance.version);
+    if let Some(desc) = &instance.description {
+        info!("  Description: {desc}");
+    }
+    info!(
+        "  Created: {}",
+        format_timestamp(instance.created, time_format)
+    );
+    if let Some(used) = instance.last_used {
+        info!(
+            "  Last used: {} ({})",
+            format_timestamp(used, time_format),
+            format_relative(used, Utc::now())
+        );
+    }
+    match &instance.mods.loader_version {
+        Some(loader_version) => info!("  Mod loader: {:?} {loader_version}", instance.mods.loader),
+        None => info!("  Mod loader: {:?}", instance.mods.loader),
+    }
+    print_mods(&instance.mods.mods);
+    if let Some(server) = &instance.settings.server {
+        match server.port {
+            Some(port) => info!("  Server: {}:{port}", server.address),
+            None => info!("  Server: {}", server.address),
+        }
+    }
+    if let Some(memory) = instance.settings.memory_mb {
+        info!("  Memory: {memory}MB");
+    }
+    if !instance.settings.java_args.is_empty() {
+        info!("  Java args: {}", instance.settings.java_args.join(" "));
+    }
+    if let Some(world) = &instance.settings.quick_play_world {
+        info!("  Quick play world: {world}");
+    }
+    if let Some(wrapper) = &instance.settings.wrapper {
+        info!("  Wrapper: {}", wrapper.join(" "));
+    }
+    if !instance.settings.env.is_empty() {
+        info!("  Environment:");
+        print_env_vars(&instance.settings.env);
+    }
+    if instance.settings.strict_java {
+        info!("  Java: exact required version only");
+    }
+    if instance.settings.demo {
+        info!("  Demo mode: on");
+    }
+    if instance.settings.backup_before_launch {
+        info!(
+            "  World backups: before each launch (keeping {})",
+            instance.settings.backup_limit()
+        );
+    }
+    Ok(())
+}
+
+/// Prints an instance's recorded mods with their versions and enabled state
+fn print_mods(mods: &[crate::launcher::ModInfo]) {
+    if mods.is_empty() {
+        return;
+    }
+    let enabled = mods.iter().filter(|m| m.enabled).count();
+    info!("  Mods ({enabled} of {} enabled):", mods.len());
+    for m in mods {
+        if m.enabled {
+            info!("    ✓ {} {} ({})", m.name, m.version, m.file_name);
+        } else {
+            info!("      {} {} ({}, disabled)", m.name, m.version, m.file_name);
+        }
+    }
+}
+
+/// Sets environment variables for an instance, or lists them when none are given
+async fn set_instance_env(
+    launcher: &Launcher,
+    name: &str,
+    vars: &[(String, String)],
+) -> crate::error::Result<()> {
+    let mut instance_manager = launcher.instance_manager.lock().await;
+
+    if vars.is_empty() {
+        let Some(instance) = instance_manager.get_instance(name) else {
+            return Err(crate::error::InstanceError::not_found(format!(
+                "Instance '{name}' does not exist"
+            ))
+            .into());
+        };
+        if instance.settings.env.is_empty() {
+            info!("Instance '{name}' has no environment variables set");
+        } else {
+            info!("Environment for instance '{name}':");
+            print_env_vars(&instance.settings.env);
+        }
+        return Ok(());
+    }
+
+    instance_manager.set_instance_env(name, vars).await?;
+    for (key, value) in vars {
+        if value.is_empty() {
+            info!("✓ Removed {key} from instance '{name}'");
+        } else {
+            info!("✓ Set {key}={value} for instance '{name}'");
+        }
     }
     Ok(())
 }
+
+/// Prints environment variables sorted by key
+fn print_env_vars(env: &std::collections::HashMap<String, String>) {
+    let mut vars: Vec<_> = env.iter().collect();
+    vars.sort();
+    for (key, value) in vars {
+        info!("    {key}={value}");
+    }
+}
+
+/// What to do when creating an instance whose name is already taken
+#[derive(Debug, Clone, Copy)]
+enum OnExisting {
+    Fail,
+    /// Delete the existing instance first, optionally moving its worlds to the new one
+    Replace {
+        keep_saves: bool,
+    },
+}
+
+/// Creates an instance, keeping network I/O outside the instance manager lock
+async fn create_instance(
+    launcher: &Launcher,
+    name: String,
+    version: String,
+    description: Option<String>,
+    existing: OnExisting,
+) -> crate::error::Result<()> {
+    crate::launcher::InstanceManager::validate_instance_name(&name)?;
+
+    // Fail fast on an existing name without waiting on the network
+    if matches!(existing, OnExisting::Fail)
+        && launcher
+            .instance_manager
+            .lock()
+            .await
+            .get_instance(&name)
+            .is_some()
+    {
+        return Err(crate::error::InstanceError::already_exists(format!(
+            "Instance '{name}' already exists (use --force to replace it)"
+        ))
+        .into());
+    }
+
+    // Fetch the manifest before taking the lock so a slow network
+    // doesn't stall other instance operations
+    let manifest = launcher.file_manager.get_version_manifest().await?;
+
+    // create_instance re-checks for a name collision under the lock
+    let mut instance_manager = launcher.instance_manager.lock().await;
+    let mut kept_saves = None;
+    if let OnExisting::Replace { keep_saves } = existing
+        && instance_manager.get_instance(&name).is_some()
+    {
+        // Nothing is deleted until the replacement is known to be valid
+        crate::launcher::InstanceManager::validate_version(&version, manifest)?;
+        let saves = instance_manager.get_instance_dir(&name).join("saves");
+        if keep_saves && saves.exists() {
+            let aside = launcher
+                .minecraft_dir
+                .instances_dir()
+                .join(format!(".{name}-saves"));
+            std::fs::rename(&saves, &aside)
+                .with_context(|| format!("Failed to set aside worlds from {}", saves.display()))?;
+            kept_saves = Some(aside);
+        }
+        with_kept_saves(
+            instance_manager.delete_instance(&name).await,
+            kept_saves.as_deref(),
+        )?;
+        info!("✓ Deleted existing instance '{name}'");
+    }
+
+    with_kept_saves(
+        instance_manager
+            .create_instance(name.clone(), version, description, manifest)
+            .await,
+        kept_saves.as_deref(),
+    )?;
+
+    if let Some(aside) = kept_saves {
+        let saves = instance_manager.get_instance_dir(&name).join("saves");
+        // The fresh instance's empty saves folder makes way for the kept one
+        let _ = std::fs::remove_dir(&saves);
+        std::fs::rename(&aside, &saves).with_context(|| {
+            format!("Failed to restore worlds; they are in {}", aside.display())
+        })?;
+        info!("✓ Kept the previous instance's worlds");
+    }
+    info!("✓ Created instance '{name}'");
+    Ok(())
+}
+
+/// Says where a replaced instance's worlds were set aside if replacing it failed
+fn with_kept_saves<T>(
+    result: crate::error::Result<T>,
+    kept_saves: Option<&std::path::Path>,
+) -> crate::error::Result<T> {
+    match kept_saves {
+        Some(aside) => result.with_context(|| {
+            format!(
+                "Failed to replace the instance; its worlds are in {}",
+                aside.display()
+            )
+        }),
+        None => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative() {
+        let now = Utc::now();
+        let ago = |seconds| format_relative(now - chrono::Duration::seconds(seconds), now);
+
+        assert_eq!(ago(-5), "just now");
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(2 * 3600 + 5), "2 hours ago");
+        assert_eq!(ago(86_400), "1 day ago");
+        assert_eq!(ago(90 * 86_400), "3 months ago");
+        assert_eq!(ago(800 * 86_400), "2 years ago");
+    }
+}
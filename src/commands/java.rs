@@ -1,28 +1,91 @@
-use std::borrow::Borrow;
-
 use crate::cli::JavaCommands;
-use tracing::info;
-
-pub fn handle_java_command(launcher: &crate::launcher::Launcher, action: JavaCommands) {
-    // Lazily initialize Java installations for Java commands
-    let java_manager = launcher.java_manager.borrow();
+use crate::error::ResultExt;
+use crate::launcher::JavaManager;
+use tracing::{info, warn};
 
+pub async fn handle_java_command(
+    launcher: &crate::launcher::Launcher,
+    action: JavaCommands,
+) -> crate::error::Result<()> {
     match action {
-        JavaCommands::List => {
-            let installations = &java_manager.installations;
-            if installations.is_empty() {
-                info!("No Java installations found. Try installing Java or setting JAVA_HOME.");
-            } else {
-                info!("Found {} Java installation(s):", installations.len());
-                for (major, installation) in installations {
-                    info!("  Java {}: {}", major, installation.path.display());
-                }
-            }
+        JavaCommands::List { json: false } => list_installations(launcher.java_manager().await),
+        JavaCommands::List { json: true } => {
+            let mut installations: Vec<_> = launcher
+                .java_manager()
+                .await
+                .installations
+                .values()
+                .collect();
+            installations.sort_by_key(|installation| installation.major_version);
+            let json = serde_json::to_string_pretty(&installations)
+                .context("Failed to serialize Java installations")?;
+            println!("{json}");
+        }
+        JavaCommands::Rescan => {
+            let mut java_manager = JavaManager::new();
+            java_manager
+                .rescan(&launcher.minecraft_dir.java_cache_path())
+                .await;
+            list_installations(&java_manager);
+        }
+        JavaCommands::Remove { target } => {
+            let cache_path = launcher.minecraft_dir.java_cache_path();
+            let mut java_manager = JavaManager::new();
+            java_manager.initialize(&cache_path, true).await;
+            let removed = java_manager.remove(&target, &cache_path)?;
+            info!(
+                "✓ Removed Java {} ({}) from the detected installations",
+                removed.major_version,
+                removed.path.display()
+            );
+            info!("💡 Run 'Redstonium java rescan' to detect it again");
         }
         JavaCommands::Recommend { version } => {
             info!("Getting recommended Java version for Minecraft {version}...");
-            let recommended = crate::launcher::JavaManager::get_required_java_version(&version);
+            let recommended = JavaManager::get_required_java_version(&version);
             info!("Recommended Java version: {recommended}");
+
+            // Any fallback from the recommended version has already been warned about
+            match launcher
+                .java_manager()
+                .await
+                .get_java_for_minecraft(&version, false)
+            {
+                Ok(installation) => info!(
+                    "✓ Would launch with Java {}: {}",
+                    installation.major_version,
+                    installation.path.display()
+                ),
+                Err(_) => warn!(
+                    "⚠ No Java installation found. Install Java {recommended} or newer, or set JAVA_HOME."
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn list_installations(java_manager: &JavaManager) {
+    let installations = &java_manager.installations;
+    if installations.is_empty() {
+        info!("No Java installations found. Try installing Java or setting JAVA_HOME.");
+    } else {
+        info!("Found {} Java installation(s):", installations.len());
+        let mut installations: Vec<_> = installations.values().collect();
+        installations.sort_by_key(|installation| installation.major_version);
+        for installation in installations {
+            match installation.details() {
+                Some(details) => info!(
+                    "  Java {}: {} ({details})",
+                    installation.major_version,
+                    installation.path.display()
+                ),
+                None => info!(
+                    "  Java {}: {}",
+                    installation.major_version,
+                    installation.path.display()
+                ),
+            }
         }
     }
 }
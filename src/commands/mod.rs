@@ -1,4 +1,27 @@
 pub mod auth;
+pub mod completions;
+pub mod disk;
+pub mod doctor;
 pub mod game;
 pub mod instance;
 pub mod java;
+pub mod versions;
+
+/// Formats a byte count with a binary unit, e.g. `1.5 GiB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
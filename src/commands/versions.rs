@@ -0,0 +1,204 @@
+use crate::error::{Result, ResultExt};
+use crate::launcher::{JavaManager, Launcher, MinecraftDir, VersionInfo};
+use chrono::{DateTime, Local};
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+/// Prints a version's metadata and how much it takes to download.
+///
+/// # Errors
+///
+/// Returns an error if the version alias cannot be resolved or its info cannot be fetched.
+pub async fn show_version_info(launcher: &Launcher, id: &str) -> Result<()> {
+    let id = super::game::resolve_version_alias(launcher, id).await?;
+    let version_info = launcher
+        .file_manager
+        .get_version_info_cached(&id, &launcher.minecraft_dir)
+        .await?;
+
+    let java = match &version_info.java_version {
+        Some(java) => java.major_version.to_string(),
+        None => format!(
+            "{} (estimated)",
+            JavaManager::get_required_java_version(&version_info.id)
+        ),
+    };
+    let installed = if launcher.minecraft_dir.is_version_installed(&id) {
+        "yes"
+    } else {
+        "no"
+    };
+
+    info!("Minecraft {}", version_info.id);
+    info!("  Type: {:?}", version_info.version_type);
+    info!("  Released: {}", version_info.release_time);
+    info!("  Java: {java}");
+    info!("  Main class: {}", version_info.main_class);
+    info!("  Libraries: {}", version_info.libraries.len());
+    info!(
+        "  Asset index: {} ({} of assets)",
+        version_info.asset_index.id,
+        super::format_size(version_info.asset_index.total_size)
+    );
+    info!(
+        "  Client JAR: {}",
+        super::format_size(version_info.downloads.client.size)
+    );
+    info!(
+        "  Download size: {}",
+        super::format_size(version_info.download_size())
+    );
+    info!("  Installed: {installed}");
+    Ok(())
+}
+
+/// A version found in the local `versions/` directory
+struct InstalledVersion {
+    id: String,
+    version_type: Option<String>,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Lists versions installed on disk with their size, without any network access.
+///
+/// # Errors
+///
+/// Returns an error if the versions directory cannot be scanned.
+pub async fn list_installed_versions(launcher: &Launcher) -> Result<()> {
+    let minecraft_dir = launcher.minecraft_dir.clone();
+    let mut versions = tokio::task::spawn_blocking(move || scan_installed_versions(&minecraft_dir))
+        .await
+        .context("Failed to scan installed versions")?;
+
+    if versions.is_empty() {
+        info!("No versions installed. Prepare one with: Redstonium prepare <version>");
+        return Ok(());
+    }
+
+    versions.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+    info!("Installed versions:");
+    for version in &versions {
+        let version_type = version
+            .version_type
+            .as_deref()
+            .map(|t| format!(" ({t})"))
+            .unwrap_or_default();
+        let modified = version
+            .modified
+            .map(|time| {
+                let time: DateTime<Local> = time.into();
+                format!(", modified {}", time.format("%Y-%m-%d %H:%M"))
+            })
+            .unwrap_or_default();
+        info!(
+            "  {}{version_type} - {}{modified}",
+            version.id,
+            super::format_size(version.size)
+        );
+    }
+
+    let total: u64 = versions.iter().map(|v| v.size).sum();
+    info!("");
+    info!(
+        "{} versions, {} total",
+        versions.len(),
+        super::format_size(total)
+    );
+    Ok(())
+}
+
+/// Reads every version directory that has a version JSON
+fn scan_installed_versions(minecraft_dir: &MinecraftDir) -> Vec<InstalledVersion> {
+    minecraft_dir
+        .installed_version_ids()
+        .into_iter()
+        .map(|id| {
+            let json_path = minecraft_dir.version_json_path(&id);
+            let version_type = match read_version_type(&json_path) {
+                Ok(version_type) => Some(version_type),
+                Err(e) => {
+                    warn!("⚠ Could not read version JSON for {id}: {e}");
+                    None
+                }
+            };
+
+            InstalledVersion {
+                size: MinecraftDir::directory_size(&minecraft_dir.version_dir(&id)),
+                modified: std::fs::metadata(&json_path)
+                    .and_then(|m| m.modified())
+                    .ok(),
+                version_type,
+                id,
+            }
+        })
+        .collect()
+}
+
+/// The type of an installed version, or the version a mod loader profile builds on
+///
+/// Loader profiles (e.g. Quilt's) only list what they add to the vanilla version they
+/// inherit from, so they aren't full version JSONs.
+fn read_version_type(json_path: &std::path::Path) -> Result<String> {
+    let content = std::fs::read_to_string(json_path)?;
+    let value: serde_json::Value = serde_json::from_str(&content)?;
+    if let Some(parent) = value.get("inheritsFrom").and_then(|parent| parent.as_str()) {
+        return Ok(format!("loader profile for {parent}"));
+    }
+    let info: VersionInfo = serde_json::from_value(value)?;
+    Ok(format!("{:?}", info.version_type).to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_installed_versions_includes_loader_profiles() {
+        let base_path =
+            std::env::temp_dir().join(format!("redstonium-versions-{}", uuid::Uuid::new_v4()));
+        let minecraft_dir = MinecraftDir::at(&base_path).unwrap();
+        let download = serde_json::json!({"sha1": "00", "size": 0, "url": ""});
+        let versions = [
+            (
+                "1.20.1",
+                serde_json::json!({
+                    "id": "1.20.1", "type": "release", "time": "", "releaseTime": "",
+                    "downloads": {"client": download}, "libraries": [],
+                    "mainClass": "net.minecraft.client.main.Main", "assets": "5",
+                    "assetIndex": {"id": "5", "sha1": "00", "size": 0, "totalSize": 0, "url": ""}
+                }),
+            ),
+            (
+                "quilt-loader-0.26.0-1.20.1",
+                serde_json::json!({
+                    "id": "quilt-loader-0.26.0-1.20.1", "inheritsFrom": "1.20.1",
+                    "mainClass": "org.quiltmc.loader.impl.launch.knot.KnotClient",
+                    "libraries": []
+                }),
+            ),
+        ];
+        for (id, json) in &versions {
+            std::fs::create_dir_all(minecraft_dir.version_dir(id)).unwrap();
+            std::fs::write(minecraft_dir.version_json_path(id), json.to_string()).unwrap();
+        }
+
+        let installed = scan_installed_versions(&minecraft_dir);
+        let types: Vec<_> = installed
+            .iter()
+            .map(|v| (v.id.as_str(), v.version_type.as_deref()))
+            .collect();
+        assert_eq!(
+            types,
+            [
+                ("1.20.1", Some("release")),
+                (
+                    "quilt-loader-0.26.0-1.20.1",
+                    Some("loader profile for 1.20.1")
+                ),
+            ]
+        );
+        std::fs::remove_dir_all(&base_path).unwrap();
+    }
+}
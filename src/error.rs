@@ -5,23 +5,28 @@ use thiserror::Error;
 #[derive(Debug, Error)]
 pub enum RustifiedError {
     /// Authentication-related errors
-    Auth(AuthError),
+    Auth(#[source] AuthError),
     /// File system and I/O errors
-    Io(std::io::Error),
+    Io(#[source] std::io::Error),
     /// Network and HTTP errors
-    Network(reqwest::Error),
+    Network(#[source] reqwest::Error),
     /// JSON parsing errors
-    Json(serde_json::Error),
+    Json(#[source] serde_json::Error),
     /// Java-related errors
-    Java(JavaError),
+    Java(#[source] JavaError),
     /// Game launching errors
-    Game(GameError),
+    Game(#[source] GameError),
     /// Instance management errors
-    Instance(InstanceError),
+    Instance(#[source] InstanceError),
     /// File management errors
-    FileManager(FileManagerError),
+    FileManager(#[source] FileManagerError),
     /// Generic errors with custom messages
     Generic(String),
+    /// An error with a description of what was being done when it happened
+    Contextual {
+        context: String,
+        source: Box<RustifiedError>,
+    },
 }
 
 /// Authentication-specific errors
@@ -88,6 +93,15 @@ pub enum GameError {
     /// Libraries download failed
     #[error("Libraries download failed: {0}")]
     LibrariesDownload(String),
+    /// The game ran past the launch timeout and was killed
+    #[error("Launch timed out: {0}")]
+    LaunchTimedOut(String),
+    /// No graphical session to open the game window in
+    #[error("No display available: {0}")]
+    DisplayUnavailable(String),
+    /// The instance is already running in another process
+    #[error("Instance already running: {0}")]
+    AlreadyRunning(String),
 }
 
 /// Instance management errors
@@ -132,7 +146,24 @@ pub type Result<T> = std::result::Result<T, RustifiedError>;
 
 impl fmt::Display for RustifiedError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let error_msg = match self {
+        write!(f, "[{}] {}", self.code(), self.message())?;
+
+        // User mistakes are explained by the message itself; only unexpected
+        // failures point at the issue tracker
+        if !self.is_user_error() {
+            write!(
+                f,
+                "\n\nIf this error persists, please consider opening an issue at: https://github.com/OmarAfet/Redstonium/issues"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl RustifiedError {
+    /// The error message without the code or issue-tracker footer
+    fn message(&self) -> String {
+        match self {
             Self::Auth(e) => e.to_string(),
             Self::Io(e) => format!("I/O error: {e}"),
             Self::Network(e) => format!("Network error: {e}"),
@@ -142,12 +173,136 @@ impl fmt::Display for RustifiedError {
             Self::Instance(e) => e.to_string(),
             Self::FileManager(e) => e.to_string(),
             Self::Generic(msg) => msg.clone(),
-        };
+            Self::Contextual { context, source } => format!("{context}: {}", source.message()),
+        }
+    }
+
+    /// The innermost error, beneath any added context
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::Contextual { source, .. } => source.root(),
+            _ => self,
+        }
+    }
+
+    /// Stable, machine-readable code for this kind of error (e.g. `R301`)
+    ///
+    /// The hundreds digit is the category: 0 general, 1 auth, 2 Java, 3 game,
+    /// 4 instance and 5 file management. Codes are never reused.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Generic(_) => "R001",
+            Self::Io(_) => "R002",
+            Self::Network(_) => "R003",
+            Self::Json(_) => "R004",
+            Self::Auth(e) => e.code(),
+            Self::Java(e) => e.code(),
+            Self::Game(e) => e.code(),
+            Self::Instance(e) => e.code(),
+            Self::FileManager(e) => e.code(),
+            Self::Contextual { source, .. } => source.code(),
+        }
+    }
+
+    /// Whether the user can fix this error themselves (a typo, missing Java, no
+    /// network) rather than it being an unexpected failure worth reporting
+    pub fn is_user_error(&self) -> bool {
+        match self {
+            Self::Contextual { source, .. } => source.is_user_error(),
+            Self::Network(_) => true,
+            Self::Generic(_) | Self::Io(_) | Self::Json(_) | Self::FileManager(_) => false,
+            Self::Auth(e) => matches!(
+                e,
+                AuthError::GameOwnership(_)
+                    | AuthError::ProfileRetrieval(_)
+                    | AuthError::OAuthError(_)
+            ),
+            Self::Java(e) => matches!(e, JavaError::NotFound(_) | JavaError::UnsupportedVersion(_)),
+            Self::Game(e) => matches!(
+                e,
+                GameError::VersionNotFound(_)
+                    | GameError::InvalidVersion(_)
+                    | GameError::PreparationFailed(_)
+                    | GameError::LaunchTimedOut(_)
+                    | GameError::DisplayUnavailable(_)
+                    | GameError::AlreadyRunning(_)
+            ),
+            Self::Instance(e) => matches!(
+                e,
+                InstanceError::NotFound(_)
+                    | InstanceError::AlreadyExists(_)
+                    | InstanceError::InvalidConfig(_)
+            ),
+        }
+    }
+}
+
+impl AuthError {
+    /// Stable error code for this variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MicrosoftAuth(_) => "R101",
+            Self::XboxAuth(_) => "R102",
+            Self::MinecraftAuth(_) => "R103",
+            Self::GameOwnership(_) => "R104",
+            Self::ProfileRetrieval(_) => "R105",
+            Self::CacheError(_) => "R106",
+            Self::OAuthError(_) => "R107",
+        }
+    }
+}
+
+impl JavaError {
+    /// Stable error code for this variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "R201",
+            Self::VersionParsing(_) => "R202",
+            Self::ExecutionFailed(_) => "R203",
+            Self::UnsupportedVersion(_) => "R204",
+        }
+    }
+}
+
+impl GameError {
+    /// Stable error code for this variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::VersionNotFound(_) => "R301",
+            Self::InvalidVersion(_) => "R302",
+            Self::PreparationFailed(_) => "R303",
+            Self::LaunchFailed(_) => "R304",
+            Self::AssetsDownload(_) => "R305",
+            Self::LibrariesDownload(_) => "R306",
+            Self::LaunchTimedOut(_) => "R307",
+            Self::DisplayUnavailable(_) => "R308",
+            Self::AlreadyRunning(_) => "R309",
+        }
+    }
+}
 
-        write!(
-            f,
-            "{error_msg}\n\nIf this error persists, please consider opening an issue at: https://github.com/OmarAfet/Redstonium/issues"
-        )
+impl InstanceError {
+    /// Stable error code for this variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "R401",
+            Self::AlreadyExists(_) => "R402",
+            Self::InvalidConfig(_) => "R403",
+            Self::CreationFailed(_) => "R404",
+            Self::DeletionFailed(_) => "R405",
+        }
+    }
+}
+
+impl FileManagerError {
+    /// Stable error code for this variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DownloadFailed(_) => "R501",
+            Self::VerificationFailed(_) => "R502",
+            Self::ExtractionFailed(_) => "R503",
+            Self::DirectoryCreation(_) => "R504",
+        }
     }
 }
 
@@ -246,12 +401,6 @@ impl From<tokio::sync::oneshot::error::RecvError> for RustifiedError {
     }
 }
 
-impl From<Box<dyn std::error::Error>> for RustifiedError {
-    fn from(err: Box<dyn std::error::Error>) -> Self {
-        Self::Generic(err.to_string())
-    }
-}
-
 impl From<zip::result::ZipError> for RustifiedError {
     fn from(err: zip::result::ZipError) -> Self {
         Self::FileManager(FileManagerError::ExtractionFailed(format!(
@@ -268,12 +417,13 @@ impl RustifiedError {
     }
 
     /// Add context to an error (similar to `anyhow::Context`)
+    ///
+    /// The original error is kept as the source, so its variant and code survive.
     #[must_use]
     pub fn with_context(self, context: impl Into<String>) -> Self {
-        let context_msg = context.into();
-        match self {
-            Self::Generic(msg) => Self::Generic(format!("{context_msg}: {msg}")),
-            _ => Self::Generic(format!("{context_msg}: {self}")),
+        Self::Contextual {
+            context: context.into(),
+            source: Box::new(self),
         }
     }
 }
@@ -367,6 +517,21 @@ impl GameError {
     pub fn libraries_download(msg: impl Into<String>) -> Self {
         Self::LibrariesDownload(msg.into())
     }
+
+    /// Create a new launch timed out error
+    pub fn launch_timed_out(msg: impl Into<String>) -> Self {
+        Self::LaunchTimedOut(msg.into())
+    }
+
+    /// Create a new display unavailable error
+    pub fn display_unavailable(msg: impl Into<String>) -> Self {
+        Self::DisplayUnavailable(msg.into())
+    }
+
+    /// Create a new already running error
+    pub fn already_running(msg: impl Into<String>) -> Self {
+        Self::AlreadyRunning(msg.into())
+    }
 }
 
 impl InstanceError {
@@ -470,3 +635,43 @@ where
         self.map_err(|e| e.into().with_context(msg))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_footer_only_for_unexpected_errors() {
+        let user_error: RustifiedError = GameError::version_not_found("1.99").into();
+        assert_eq!(user_error.code(), "R301");
+        assert_eq!(user_error.to_string(), "[R301] Version not found: 1.99");
+
+        let bug: RustifiedError = FileManagerError::extraction_failed("bad zip").into();
+        assert_eq!(bug.code(), "R503");
+        assert!(bug.to_string().contains("opening an issue"));
+    }
+
+    #[test]
+    fn test_context_preserves_source() {
+        let err = RustifiedError::from(InstanceError::not_found("survival"))
+            .with_context("Failed to launch")
+            .with_context("Launch aborted");
+
+        assert_eq!(err.code(), "R401");
+        assert!(err.is_user_error());
+        assert_eq!(
+            err.to_string(),
+            "[R401] Launch aborted: Failed to launch: Instance not found: survival"
+        );
+        assert!(matches!(
+            err.root(),
+            RustifiedError::Instance(InstanceError::NotFound(_))
+        ));
+
+        let mut source = std::error::Error::source(&err);
+        while let Some(next) = source.and_then(std::error::Error::source) {
+            source = Some(next);
+        }
+        assert!(source.is_some_and(|e| e.downcast_ref::<InstanceError>().is_some()));
+    }
+}
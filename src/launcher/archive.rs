@@ -0,0 +1,160 @@
+use crate::error::{FileManagerError, Result, ResultExt};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Zip the contents of a directory, skipping the named top-level entries
+///
+/// Paths inside the archive are relative to `source` and always use `/`.
+/// Returns the number of files written.
+pub fn zip_directory(source: &Path, destination: &Path, exclude: &[&str]) -> Result<usize> {
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let file = File::create(destination)
+        .with_context(|| format!("Failed to create archive: {}", destination.display()))?;
+    let mut writer = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut count = 0;
+    let mut pending = vec![source.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(relative) = path.strip_prefix(source) else {
+                continue;
+            };
+            // The archive being written may live inside the directory itself
+            if path == destination {
+                continue;
+            }
+            if dir == source
+                && exclude
+                    .iter()
+                    .any(|name| entry.file_name().to_string_lossy() == *name)
+            {
+                continue;
+            }
+
+            let name = relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            if path.is_dir() {
+                writer.add_directory(format!("{name}/"), options)?;
+                pending.push(path);
+            } else {
+                writer.start_file(name, options)?;
+                let mut input = File::open(&path)
+                    .with_context(|| format!("Failed to open file: {}", path.display()))?;
+                std::io::copy(&mut input, &mut writer)
+                    .with_context(|| format!("Failed to archive file: {}", path.display()))?;
+                count += 1;
+            }
+        }
+    }
+
+    writer.finish()?.flush()?;
+    Ok(count)
+}
+
+/// Read a single file from an archive, if present
+pub fn read_archive_file(archive_path: &Path, name: &str) -> Result<Option<String>> {
+    let mut archive = open_archive(archive_path)?;
+    let Ok(mut file) = archive.by_name(name) else {
+        return Ok(None);
+    };
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .with_context(|| format!("Failed to read {name} from {}", archive_path.display()))?;
+    Ok(Some(content))
+}
+
+/// Extract an archive into a directory
+///
+/// Entries that would escape the destination (e.g. `../`) are rejected.
+pub fn extract_archive(archive_path: &Path, destination: &Path) -> Result<()> {
+    let mut archive = open_archive(archive_path)?;
+
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .with_context(|| format!("Failed to read entry {i} from archive"))?;
+        let Some(relative) = file.enclosed_name().map(Path::to_path_buf) else {
+            return Err(FileManagerError::extraction_failed(format!(
+                "Archive entry '{}' points outside the destination",
+                file.name()
+            ))
+            .into());
+        };
+        let output_path = destination.join(relative);
+
+        if file.is_dir() {
+            std::fs::create_dir_all(&output_path).with_context(|| {
+                format!("Failed to create directory: {}", output_path.display())
+            })?;
+            continue;
+        }
+
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let mut output = File::create(&output_path)
+            .with_context(|| format!("Failed to create file: {}", output_path.display()))?;
+        std::io::copy(&mut file, &mut output)
+            .with_context(|| format!("Failed to extract file: {}", output_path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn open_archive(archive_path: &Path) -> Result<ZipArchive<File>> {
+    let file = File::open(archive_path)
+        .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
+    ZipArchive::new(file)
+        .with_context(|| format!("Failed to read archive: {}", archive_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zip_directory_round_trip_with_exclusions() {
+        let base = std::env::temp_dir().join(format!("redstonium-archive-{}", std::process::id()));
+        let source = base.join("source");
+        std::fs::create_dir_all(source.join("saves/World")).unwrap();
+        std::fs::create_dir_all(source.join("logs")).unwrap();
+        std::fs::write(source.join("options.txt"), "lang:en_us\n").unwrap();
+        std::fs::write(source.join("saves/World/level.dat"), "data").unwrap();
+        std::fs::write(source.join("logs/latest.log"), "log").unwrap();
+
+        let archive = base.join("backup.zip");
+        let count = zip_directory(&source, &archive, &["logs"]).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            read_archive_file(&archive, "options.txt")
+                .unwrap()
+                .as_deref(),
+            Some("lang:en_us\n")
+        );
+
+        let restored = base.join("restored");
+        extract_archive(&archive, &restored).unwrap();
+        assert!(restored.join("saves/World/level.dat").is_file());
+        assert!(!restored.join("logs").exists());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use crate::error::{Result, ResultExt};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::launcher::minecraft_dir::MinecraftDir;
+
+/// Launcher-wide settings stored in `redstonium.json` in the Minecraft directory
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LauncherConfig {
+    /// JVM arguments applied to every instance; instance `java_args` take precedence
+    pub jvm_args: Vec<String>,
+    /// Launcher name reported to the game, for forks and rebrands
+    pub brand: Option<String>,
+    /// Number of assets downloaded at once; adapts to the connection when unset
+    pub asset_batch_size: Option<usize>,
+    /// Architecture to select natives and Java for (e.g. `x86_64` under Rosetta)
+    pub target_arch: Option<String>,
+    /// Sign in again when the cached login expires within this many minutes
+    pub auth_refresh_minutes: Option<u32>,
+    /// Wait for a keypress after the game exits, as if `--keep-open` were always passed
+    pub keep_open: bool,
+    /// Version manifest URL, for mirrors; Mojang's when unset
+    pub manifest_url: Option<String>,
+    /// Asset download base URL, for mirrors; Mojang's when unset
+    pub resources_url: Option<String>,
+    /// Reuse the official launcher's libraries and assets when preparing into `--output-dir`
+    pub link_official: bool,
+}
+
+impl LauncherConfig {
+    /// The launcher name reported to the game
+    pub fn brand(&self) -> &str {
+        self.brand
+            .as_deref()
+            .filter(|brand| !brand.trim().is_empty())
+            .unwrap_or(crate::launcher::LAUNCHER_BRAND)
+    }
+
+    /// How close to expiry a cached login is refreshed before launching
+    pub fn auth_refresh_threshold(&self) -> time::Duration {
+        const DEFAULT_AUTH_REFRESH_MINUTES: u32 = 15;
+        time::Duration::minutes(
+            self.auth_refresh_minutes
+                .unwrap_or(DEFAULT_AUTH_REFRESH_MINUTES)
+                .into(),
+        )
+    }
+
+    /// Apply the configured target architecture unless one was already set
+    pub fn apply_target_arch(&self) {
+        let Some(arch) = self.target_arch.as_deref() else {
+            return;
+        };
+        match crate::launcher::version::normalize_arch(arch) {
+            Some(arch) => crate::launcher::version::set_target_arch(arch),
+            None => warn!("⚠ Ignoring unknown target_arch '{arch}' in launcher config"),
+        }
+    }
+
+    /// Load the launcher config, falling back to defaults if it is missing or invalid
+    pub fn load(minecraft_dir: &MinecraftDir) -> Self {
+        let path = minecraft_dir.launcher_config_path();
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::read(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!(
+                    "⚠ Ignoring invalid launcher config at {}: {e}",
+                    path.display()
+                );
+                Self::default()
+            }
+        }
+    }
+
+    fn read(path: &std::path::Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).context("Failed to read launcher config")?;
+        let config = serde_json::from_str(&content).context("Failed to parse launcher config")?;
+        Ok(config)
+    }
+}
@@ -1,9 +1,13 @@
-use crate::error::{FileManagerError, Result, ResultExt};
+use crate::error::{FileManagerError, GameError, Result, ResultExt};
 use crate::launcher;
+use crate::launcher::timings::Timings;
 use reqwest::Client;
 use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha512};
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::path::Path;
+use std::sync::Arc;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use tracing::{debug, info, warn};
@@ -12,27 +16,213 @@ use zip::ZipArchive;
 use crate::launcher::minecraft_dir::MinecraftDir;
 use crate::launcher::version::{AssetManifest, Library, VersionInfo, VersionManifest};
 
-const VERSION_MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
+/// Equivalent hosts serving Mojang's version manifest; the fastest to answer is used
+const VERSION_MANIFEST_URLS: [&str; 2] = [
+    "https://piston-meta.mojang.com/mc/game/version_manifest.json",
+    "https://launchermeta.mojang.com/mc/game/version_manifest.json",
+];
+/// How long to wait for any manifest host before falling back to the first one
+const MANIFEST_RACE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 const RESOURCES_BASE_URL: &str = "https://resources.download.minecraft.net";
 
+/// Expected hash of a downloaded file, as lowercase hex
+///
+/// Mojang's files use SHA1; mod and pack sources also publish SHA256 or SHA512.
+#[derive(Debug, Clone, Copy)]
+pub enum Checksum<'a> {
+    Sha1(&'a str),
+    Sha256(&'a str),
+    Sha512(&'a str),
+}
+
+impl<'a> Checksum<'a> {
+    /// Parse `<algorithm>:<hex>`, e.g. `sha256:9f86d0...`
+    pub fn parse(value: &'a str) -> Result<Self> {
+        let (algorithm, hash) = value.split_once(':').unwrap_or(("", value));
+        match algorithm.to_ascii_lowercase().as_str() {
+            "sha1" => Ok(Self::Sha1(hash)),
+            "sha256" => Ok(Self::Sha256(hash)),
+            "sha512" => Ok(Self::Sha512(hash)),
+            _ => Err(FileManagerError::validation_failed(format!(
+                "Unsupported checksum '{value}'; expected sha1:<hex>, sha256:<hex> or sha512:<hex>"
+            ))
+            .into()),
+        }
+    }
+
+    /// Check `data` against the expected hash
+    pub fn verify(self, data: &[u8]) -> Result<()> {
+        let (name, expected, actual) = match self {
+            Self::Sha1(expected) => ("SHA1", expected, format!("{:x}", Sha1::digest(data))),
+            Self::Sha256(expected) => ("SHA256", expected, format!("{:x}", Sha256::digest(data))),
+            Self::Sha512(expected) => ("SHA512", expected, format!("{:x}", Sha512::digest(data))),
+        };
+        if actual.eq_ignore_ascii_case(expected) {
+            return Ok(());
+        }
+        Err(FileManagerError::validation_failed(format!(
+            "{name} mismatch: expected {expected}, got {actual}"
+        ))
+        .into())
+    }
+}
+
+/// Bounds for adaptive asset download concurrency
+const MIN_ASSET_BATCH: usize = 8;
+const INITIAL_ASSET_BATCH: usize = 32;
+const MAX_ASSET_BATCH: usize = 256;
+
 pub struct FileManager {
     client: Client,
+    /// Where the version manifest is fetched from, if pinned
+    manifest_url: Option<String>,
+    /// Fastest of Mojang's manifest hosts, picked on first use when not pinned
+    fastest_manifest_url: tokio::sync::OnceCell<String>,
+    /// Base URL of the content-addressed asset store
+    resources_url: String,
+    /// Fixed number of concurrent asset downloads; adaptive when `None`
+    asset_batch_size: Option<usize>,
+    /// Pick the asset batch size from measured throughput, overriding `asset_batch_size`
+    auto_tune: bool,
+    /// Version manifest, fetched at most once per run
+    manifest: tokio::sync::OnceCell<VersionManifest>,
+    /// Version JSON fetched this run, saved verbatim so it keeps the manifest's hash
+    fetched_json: std::sync::Mutex<HashMap<String, String>>,
+    /// Whether version JSON already on disk may be used instead of fetching it
+    use_cache: bool,
+    /// Where manifest and version info fetch durations are recorded
+    timings: Arc<Timings>,
+}
+
+/// Builds a [`FileManager`] with non-default endpoints or download settings
+///
+/// Unset endpoints default to Mojang's servers.
+#[derive(Debug, Default)]
+pub struct FileManagerBuilder {
+    manifest_url: Option<String>,
+    resources_url: Option<String>,
+    asset_batch_size: Option<usize>,
+    auto_tune: bool,
+    no_cache: bool,
+    timings: Option<Arc<Timings>>,
+}
+
+impl FileManagerBuilder {
+    /// Fetch the version manifest from this URL, e.g. a mirror
+    pub fn manifest_url(mut self, url: impl Into<String>) -> Self {
+        self.manifest_url = Some(url.into());
+        self
+    }
+
+    /// Download assets from this base URL instead of Mojang's resource server
+    pub fn resources_url(mut self, url: impl Into<String>) -> Self {
+        self.resources_url = Some(url.into());
+        self
+    }
+
+    /// Use a fixed asset download batch size instead of adapting to the connection
+    pub fn asset_batch_size(mut self, batch_size: Option<usize>) -> Self {
+        self.asset_batch_size = batch_size.filter(|size| *size > 0);
+        self
+    }
+
+    /// Measure throughput on the first asset batches and keep the fastest batch size
+    pub fn auto_tune(mut self, auto_tune: bool) -> Self {
+        self.auto_tune = auto_tune;
+        self
+    }
+
+    /// Whether version JSON already on disk may be reused (the default)
+    pub fn use_cache(mut self, use_cache: bool) -> Self {
+        self.no_cache = !use_cache;
+        self
+    }
+
+    /// Record fetch durations in these shared timings
+    pub fn timings(mut self, timings: Arc<Timings>) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
+    pub fn build(self) -> FileManager {
+        FileManager {
+            client: Client::new(),
+            manifest_url: self.manifest_url,
+            fastest_manifest_url: tokio::sync::OnceCell::new(),
+            resources_url: self.resources_url.map_or_else(
+                || RESOURCES_BASE_URL.to_string(),
+                |url| url.trim_end_matches('/').to_string(),
+            ),
+            asset_batch_size: self.asset_batch_size,
+            auto_tune: self.auto_tune,
+            manifest: tokio::sync::OnceCell::new(),
+            fetched_json: std::sync::Mutex::default(),
+            use_cache: !self.no_cache,
+            timings: self.timings.unwrap_or_default(),
+        }
+    }
 }
 
 impl FileManager {
     pub fn new() -> Self {
-        Self {
-            client: Client::new(),
+        Self::builder().build()
+    }
+
+    pub fn builder() -> FileManagerBuilder {
+        FileManagerBuilder::default()
+    }
+
+    /// Get the version manifest, fetching it from Mojang on first use
+    pub async fn get_version_manifest(&self) -> Result<&VersionManifest> {
+        self.manifest
+            .get_or_try_init(|| {
+                self.timings
+                    .time("manifest fetch", self.fetch_version_manifest())
+            })
+            .await
+    }
+
+    /// URL the version manifest is fetched from
+    ///
+    /// Unless one is pinned, HEAD requests race Mojang's equivalent hosts and the
+    /// first to answer is used for the rest of the run.
+    async fn manifest_url(&self) -> &str {
+        if let Some(url) = &self.manifest_url {
+            return url;
         }
+        self.fastest_manifest_url
+            .get_or_init(|| async {
+                let probes = VERSION_MANIFEST_URLS.map(|url| {
+                    Box::pin(async move {
+                        match self.client.head(url).send().await {
+                            Ok(response) if response.status().is_success() => Ok(url),
+                            _ => Err(()),
+                        }
+                    })
+                });
+                let fastest = tokio::time::timeout(
+                    MANIFEST_RACE_TIMEOUT,
+                    futures_util::future::select_ok(probes),
+                )
+                .await;
+                let url = match fastest {
+                    Ok(Ok((url, _))) => url,
+                    _ => VERSION_MANIFEST_URLS[0],
+                };
+                debug!("Using version manifest host {url}");
+                url.to_string()
+            })
+            .await
     }
 
     /// Fetch the version manifest from Mojang
-    pub async fn get_version_manifest(&self) -> Result<VersionManifest> {
-        info!("Fetching version manifest from {VERSION_MANIFEST_URL}");
+    async fn fetch_version_manifest(&self) -> Result<VersionManifest> {
+        let manifest_url = self.manifest_url().await;
+        info!("Fetching version manifest from {manifest_url}");
 
         let response = self
             .client
-            .get(VERSION_MANIFEST_URL)
+            .get(manifest_url)
             .send()
             .await
             .context("Failed to fetch version manifest")?;
@@ -57,6 +247,75 @@ impl FileManager {
         Ok(manifest)
     }
 
+    /// Send a HEAD request to the version manifest endpoint and return the status
+    pub async fn probe_manifest_endpoint(&self) -> Result<reqwest::StatusCode> {
+        let response = self
+            .client
+            .head(self.manifest_url().await)
+            .send()
+            .await
+            .context("Failed to reach version manifest endpoint")?;
+
+        Ok(response.status())
+    }
+
+    /// Fetch and parse a JSON document, e.g. from a mod loader's meta API
+    pub async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {url}"))?;
+        if !response.status().is_success() {
+            return Err(FileManagerError::download_failed(format!(
+                "Failed to fetch {url}: HTTP {}",
+                response.status()
+            ))
+            .into());
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse JSON from {url}"))
+    }
+
+    /// Download a file to `destination`, e.g. a user-supplied pack, verifying it if a
+    /// checksum is given
+    pub async fn download_to(
+        &self,
+        url: &str,
+        destination: &Path,
+        checksum: Option<Checksum<'_>>,
+    ) -> Result<()> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {url}"))?;
+        if !response.status().is_success() {
+            return Err(FileManagerError::download_failed(format!(
+                "Failed to download {url}: HTTP {}",
+                response.status()
+            ))
+            .into());
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .with_context(|| format!("Failed to download {url}"))?;
+        if let Some(checksum) = checksum {
+            checksum.verify(&bytes)?;
+        }
+        fs::write(destination, &bytes)
+            .await
+            .with_context(|| format!("Failed to write file: {}", destination.display()))?;
+        Ok(())
+    }
+
     /// Get version info for a specific version
     pub async fn get_version_info(&self, version_id: &str) -> Result<VersionInfo> {
         info!("Getting version info for {version_id}");
@@ -76,6 +335,7 @@ impl FileManager {
 
         info!("Fetching version info from {}", version_entry.url);
 
+        let started = std::time::Instant::now();
         let response = self
             .client
             .get(&version_entry.url)
@@ -91,10 +351,16 @@ impl FileManager {
             .into());
         }
 
-        let version_info: VersionInfo = response
-            .json()
+        let content = response
+            .text()
             .await
+            .with_context(|| format!("Failed to fetch version info for {version_id}"))?;
+        let version_info: VersionInfo = serde_json::from_str(&content)
             .with_context(|| format!("Failed to parse version info JSON for {version_id}"))?;
+        self.timings.record("version info fetch", started.elapsed());
+        if let Ok(mut fetched) = self.fetched_json.lock() {
+            fetched.insert(version_id.to_string(), content);
+        }
 
         // Check minimum launcher version if present
         if let Some(min_version) = version_info.minimum_launcher_version {
@@ -110,6 +376,102 @@ impl FileManager {
         Ok(version_info)
     }
 
+    /// Get version info, preferring the version JSON saved by a previous download
+    ///
+    /// Falls back to the network when the local copy is missing, unreadable or
+    /// doesn't match the hash the manifest lists for it.
+    pub async fn get_version_info_cached(
+        &self,
+        version_id: &str,
+        minecraft_dir: &MinecraftDir,
+    ) -> Result<VersionInfo> {
+        let json_path = minecraft_dir.version_json_path(version_id);
+        if self.use_cache
+            && let Some(version_info) = Self::read_local_version_info(&json_path, version_id).await
+            && self.matches_manifest(&json_path, version_id).await
+        {
+            return Ok(version_info);
+        }
+
+        self.get_version_info(version_id).await
+    }
+
+    /// Whether a local version JSON has the hash the manifest lists for it
+    ///
+    /// Trusted as-is when the manifest can't be fetched, doesn't list the version
+    /// (e.g. a loader profile) or gives no hash for it.
+    async fn matches_manifest(&self, json_path: &Path, version_id: &str) -> bool {
+        let Ok(manifest) = self.get_version_manifest().await else {
+            return true;
+        };
+        let Some(expected) = manifest
+            .versions
+            .iter()
+            .find(|entry| entry.id == version_id)
+            .and_then(launcher::VersionEntry::json_sha1)
+        else {
+            return true;
+        };
+
+        if self
+            .is_file_valid(json_path, expected)
+            .await
+            .unwrap_or(false)
+        {
+            return true;
+        }
+        warn!(
+            "Local version JSON at {} doesn't match the manifest, fetching from network",
+            json_path.display()
+        );
+        false
+    }
+
+    /// Get version info from the local version JSON only, without network access
+    pub async fn get_local_version_info(
+        version_id: &str,
+        minecraft_dir: &MinecraftDir,
+    ) -> Result<VersionInfo> {
+        let json_path = minecraft_dir.version_json_path(version_id);
+        Self::read_local_version_info(&json_path, version_id)
+            .await
+            .ok_or_else(|| {
+                FileManagerError::version_not_found(&format!(
+                    "No valid local version JSON for {version_id}. Run 'Redstonium prepare {version_id}' while online first."
+                ))
+                .into()
+            })
+    }
+
+    /// Read and validate a locally stored version JSON
+    async fn read_local_version_info(json_path: &Path, version_id: &str) -> Option<VersionInfo> {
+        let content = fs::read_to_string(json_path).await.ok()?;
+
+        match serde_json::from_str::<VersionInfo>(&content) {
+            Ok(version_info) if version_info.id == version_id => {
+                debug!(
+                    "Using local version info for {version_id} from {}",
+                    json_path.display()
+                );
+                Some(version_info)
+            }
+            Ok(version_info) => {
+                warn!(
+                    "Local version JSON at {} is for {} instead of {version_id}, fetching from network",
+                    json_path.display(),
+                    version_info.id
+                );
+                None
+            }
+            Err(e) => {
+                warn!(
+                    "Local version JSON for {version_id} is corrupt ({e}), fetching from network"
+                );
+                None
+            }
+        }
+    }
+
     /// Download the main game JAR file
     pub async fn download_game_jar(
         &self,
@@ -119,9 +481,17 @@ impl FileManager {
         let jar_path = minecraft_dir.version_jar_path(&version_info.id);
         let json_path = minecraft_dir.version_json_path(&version_info.id);
 
-        // Save version JSON first
-        let version_json = serde_json::to_string_pretty(version_info)
-            .context("Failed to serialize version info")?;
+        // Save version JSON first, as fetched when possible so it matches the manifest's hash
+        let fetched = self
+            .fetched_json
+            .lock()
+            .ok()
+            .and_then(|fetched| fetched.get(&version_info.id).cloned());
+        let version_json = match fetched {
+            Some(content) => content,
+            None => serde_json::to_string_pretty(version_info)
+                .context("Failed to serialize version info")?,
+        };
 
         fs::write(&json_path, version_json)
             .await
@@ -139,7 +509,7 @@ impl FileManager {
             self.download_file_with_verification(
                 &version_info.downloads.client.url,
                 &jar_path,
-                &version_info.downloads.client.sha1,
+                Checksum::Sha1(&version_info.downloads.client.sha1),
                 version_info.downloads.client.size,
             )
             .await
@@ -151,11 +521,56 @@ impl FileManager {
         Ok(())
     }
 
-    /// Download all required libraries
+    /// Download the version's log4j configuration, if it has one
+    pub async fn download_logging_config(
+        &self,
+        version_info: &VersionInfo,
+        minecraft_dir: &MinecraftDir,
+    ) -> Result<()> {
+        let Some(config) = version_info
+            .logging
+            .as_ref()
+            .and_then(|logging| logging.client.as_ref())
+        else {
+            return Ok(());
+        };
+
+        let path = minecraft_dir.log_config_path(&config.file.id);
+        if self.is_file_valid(&path, &config.file.sha1).await? {
+            debug!(
+                "Logging config {} already exists and is valid",
+                config.file.id
+            );
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        info!("Downloading logging config {}", config.file.id);
+        self.download_file_with_verification(
+            &config.file.url,
+            &path,
+            Checksum::Sha1(&config.file.sha1),
+            config.file.size,
+        )
+        .await
+        .with_context(|| format!("Failed to download logging config {}", config.file.id))?;
+
+        Ok(())
+    }
+
+    /// Download the libraries a version needs
+    ///
+    /// Files whose SHA1 is in `known` are trusted if present instead of being re-hashed.
     pub async fn download_libraries(
         &self,
         version_info: &VersionInfo,
         minecraft_dir: &MinecraftDir,
+        known: &HashSet<String>,
     ) -> Result<()> {
         info!("Downloading libraries for {}", version_info.id);
         debug!("About to filter {} libraries", version_info.libraries.len());
@@ -186,24 +601,28 @@ impl FileManager {
             total_libraries += 1;
 
             if library.is_native_library() {
-                downloaded_libraries += self
-                    .download_native_library(library, version_info, minecraft_dir)
-                    .await?;
+                downloaded_libraries +=
+                    self.download_native_library(library, minecraft_dir).await?;
                 continue;
             }
 
             downloaded_libraries += self
-                .download_regular_library(library, minecraft_dir)
+                .download_regular_library(library, minecraft_dir, known)
                 .await?;
 
-            downloaded_libraries += self
-                .download_legacy_native(library, version_info, minecraft_dir)
-                .await?;
+            downloaded_libraries += self.download_legacy_native(library, minecraft_dir).await?;
         }
 
         info!(
             "✓ Libraries processed: {downloaded_libraries} downloaded, {skipped_libraries} skipped, {total_libraries} total"
         );
+
+        let native_jars = launcher::files::FileManager::native_jars(version_info, minecraft_dir);
+        let natives_dir = minecraft_dir.natives_dir(&version_info.id);
+        if launcher::files::FileManager::natives_outdated(&native_jars, &natives_dir)? {
+            debug!("Extracting natives for {}", version_info.id);
+            self.replace_natives(&native_jars, &natives_dir).await?;
+        }
         Ok(())
     }
 
@@ -211,7 +630,6 @@ impl FileManager {
     async fn download_native_library(
         &self,
         library: &Library,
-        version_info: &VersionInfo,
         minecraft_dir: &MinecraftDir,
     ) -> Result<u32> {
         if let Some(artifact) = &library.downloads.artifact {
@@ -232,20 +650,6 @@ impl FileManager {
                     "Native library {} already exists and is valid",
                     library.name
                 );
-                // Always check if natives need to be extracted from this library
-                let natives_dir = minecraft_dir.natives_dir(&version_info.id);
-                if launcher::files::FileManager::should_extract_natives(
-                    &full_path,
-                    &natives_dir,
-                    library,
-                )? {
-                    debug!("Extracting natives from existing JAR: {}", library.name);
-                    self.extract_natives(&full_path, &natives_dir, library)
-                        .await
-                        .with_context(|| {
-                            format!("Failed to extract natives from {}", library.name)
-                        })?;
-                }
                 Ok(0)
             } else {
                 debug!("Downloading native library: {}", library.name);
@@ -253,21 +657,12 @@ impl FileManager {
                 self.download_file_with_verification(
                     &artifact.url,
                     &full_path,
-                    &artifact.sha1,
+                    Checksum::Sha1(&artifact.sha1),
                     artifact.size,
                 )
                 .await
                 .with_context(|| format!("Failed to download native library: {}", library.name))?;
 
-                // Extract natives from the JAR
-                self.extract_natives(
-                    &full_path,
-                    &minecraft_dir.natives_dir(&version_info.id),
-                    library,
-                )
-                .await
-                .with_context(|| format!("Failed to extract natives from {}", library.name))?;
-
                 Ok(1)
             }
         } else {
@@ -280,6 +675,7 @@ impl FileManager {
         &self,
         library: &Library,
         minecraft_dir: &MinecraftDir,
+        known: &HashSet<String>,
     ) -> Result<u32> {
         if let Some(artifact) = &library.downloads.artifact {
             let lib_path = get_library_path(&library.name);
@@ -291,7 +687,10 @@ impl FileManager {
                 })?;
             }
 
-            if self.is_file_valid(&full_path, &artifact.sha1).await? {
+            if self
+                .is_file_present(&full_path, &artifact.sha1, known)
+                .await?
+            {
                 debug!("Library {} already exists and is valid", library.name);
                 Ok(0)
             } else {
@@ -300,7 +699,7 @@ impl FileManager {
                 self.download_file_with_verification(
                     &artifact.url,
                     &full_path,
-                    &artifact.sha1,
+                    Checksum::Sha1(&artifact.sha1),
                     artifact.size,
                 )
                 .await
@@ -317,7 +716,6 @@ impl FileManager {
     async fn download_legacy_native(
         &self,
         library: &Library,
-        version_info: &VersionInfo,
         minecraft_dir: &MinecraftDir,
     ) -> Result<u32> {
         if let (Some(classifiers), Some(native_classifier)) = (
@@ -341,23 +739,10 @@ impl FileManager {
                     .is_file_valid(&full_path, &native_download.sha1)
                     .await?
                 {
-                    // File exists and is valid, but still need to check if natives need extraction
-                    let natives_dir = minecraft_dir.natives_dir(&version_info.id);
-                    if launcher::files::FileManager::should_extract_natives(
-                        &full_path,
-                        &natives_dir,
-                        library,
-                    )? {
-                        debug!(
-                            "Extracting natives from existing legacy JAR: {}-{}",
-                            library.name, native_classifier
-                        );
-                        self.extract_natives(&full_path, &natives_dir, library)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to extract natives from {}", library.name)
-                            })?;
-                    }
+                    debug!(
+                        "Legacy native library {}-{} already exists and is valid",
+                        library.name, native_classifier
+                    );
                 } else {
                     debug!(
                         "Downloading legacy native library: {}-{}",
@@ -367,7 +752,7 @@ impl FileManager {
                     self.download_file_with_verification(
                         &native_download.url,
                         &full_path,
-                        &native_download.sha1,
+                        Checksum::Sha1(&native_download.sha1),
                         native_download.size,
                     )
                     .await
@@ -377,15 +762,6 @@ impl FileManager {
                             library.name, native_classifier
                         )
                     })?;
-
-                    // Extract natives
-                    self.extract_natives(
-                        &full_path,
-                        &minecraft_dir.natives_dir(&version_info.id),
-                        library,
-                    )
-                    .await
-                    .with_context(|| format!("Failed to extract natives from {}", library.name))?;
                     return Ok(1);
                 }
             }
@@ -393,50 +769,18 @@ impl FileManager {
         Ok(0)
     }
 
-    /// Download game assets with concurrent processing
+    /// Download a version's assets into the content-addressed store
+    ///
+    /// Objects whose hash is in `known` are trusted if present instead of being re-hashed.
     pub async fn download_assets(
         &self,
         version_info: &VersionInfo,
         minecraft_dir: &MinecraftDir,
+        known: &HashSet<String>,
     ) -> Result<()> {
-        const BATCH_SIZE: usize = 50;
-
         info!("Downloading assets for {}", version_info.id);
 
-        // Download asset index
-        let asset_index_path = minecraft_dir.asset_index_path(&version_info.asset_index.id);
-
-        if let Some(parent) = asset_index_path.parent() {
-            fs::create_dir_all(parent)
-                .await
-                .context("Failed to create asset indexes directory")?;
-        }
-
-        if self
-            .is_file_valid(&asset_index_path, &version_info.asset_index.sha1)
-            .await?
-        {
-            info!("Asset index already exists and is valid");
-        } else {
-            info!("Downloading asset index: {}", version_info.asset_index.id);
-
-            self.download_file_with_verification(
-                &version_info.asset_index.url,
-                &asset_index_path,
-                &version_info.asset_index.sha1,
-                version_info.asset_index.size,
-            )
-            .await
-            .context("Failed to download asset index")?;
-        }
-
-        // Parse asset index
-        let asset_index_content = fs::read_to_string(&asset_index_path)
-            .await
-            .context("Failed to read asset index")?;
-
-        let asset_manifest: AssetManifest = serde_json::from_str(&asset_index_content)
-            .context("Failed to parse asset index JSON")?;
+        let asset_manifest = self.load_asset_index(version_info, minecraft_dir).await?;
 
         // Download individual assets with concurrency
         let total_assets = asset_manifest.objects.len();
@@ -445,15 +789,25 @@ impl FileManager {
         // Process assets in batches to avoid overwhelming the server
         let mut downloaded_assets = 0;
         let mut skipped_assets = 0;
+        let mut batch_sizer = if self.auto_tune {
+            AssetBatchSizer::auto_tuned()
+        } else {
+            AssetBatchSizer::new(self.asset_batch_size)
+        };
 
         let assets: Vec<_> = asset_manifest.objects.iter().collect();
+        let mut start = 0;
+
+        while start < assets.len() {
+            let end = (start + batch_sizer.size).min(assets.len());
+            let batch = &assets[start..end];
+            start = end;
 
-        for batch in assets.chunks(BATCH_SIZE) {
             let download_futures = batch.iter().map(|(asset_name, asset_object)| {
                 let asset_path = minecraft_dir.asset_path(&asset_object.hash);
                 let asset_url = format!(
                     "{}/{}/{}",
-                    RESOURCES_BASE_URL,
+                    self.resources_url,
                     &asset_object.hash[..2],
                     &asset_object.hash
                 );
@@ -474,7 +828,7 @@ impl FileManager {
 
                     // Check if file already exists and is valid
                     if self
-                        .is_file_valid(&asset_path, &asset_object.hash)
+                        .is_file_present(&asset_path, &asset_object.hash, known)
                         .await
                         .unwrap_or(false)
                     {
@@ -485,7 +839,7 @@ impl FileManager {
                     self.download_file_with_verification(
                         &asset_url,
                         &asset_path,
-                        &asset_object.hash,
+                        Checksum::Sha1(&asset_object.hash),
                         asset_object.size,
                     )
                     .await
@@ -496,18 +850,28 @@ impl FileManager {
             });
 
             // Execute downloads concurrently
+            let started = std::time::Instant::now();
             let results = futures_util::future::join_all(download_futures).await;
+            let elapsed = started.elapsed();
 
-            for result in results {
+            let mut failures = 0;
+            let mut downloaded_bytes = 0;
+            for (result, (_, asset_object)) in results.into_iter().zip(batch) {
                 match result {
-                    Ok(true) => downloaded_assets += 1,
+                    Ok(true) => {
+                        downloaded_assets += 1;
+                        downloaded_bytes += asset_object.size;
+                    }
                     Ok(false) => skipped_assets += 1,
                     Err(e) => {
                         warn!("Asset download failed: {e}");
+                        failures += 1;
                         // Continue with other assets instead of failing completely
                     }
                 }
             }
+            batch_sizer.record(batch.len(), failures);
+            batch_sizer.record_throughput(downloaded_bytes, elapsed);
 
             // Progress update
             let processed = downloaded_assets + skipped_assets;
@@ -522,12 +886,54 @@ impl FileManager {
         Ok(())
     }
 
-    /// Download a file with SHA1 verification
+    /// Download the asset index if needed and parse it
+    async fn load_asset_index(
+        &self,
+        version_info: &VersionInfo,
+        minecraft_dir: &MinecraftDir,
+    ) -> Result<AssetManifest> {
+        let asset_index_path = minecraft_dir.asset_index_path(&version_info.asset_index.id);
+
+        if let Some(parent) = asset_index_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context("Failed to create asset indexes directory")?;
+        }
+
+        if self
+            .is_file_valid(&asset_index_path, &version_info.asset_index.sha1)
+            .await?
+        {
+            info!("Asset index already exists and is valid");
+        } else {
+            info!("Downloading asset index: {}", version_info.asset_index.id);
+
+            self.download_file_with_verification(
+                &version_info.asset_index.url,
+                &asset_index_path,
+                Checksum::Sha1(&version_info.asset_index.sha1),
+                version_info.asset_index.size,
+            )
+            .await
+            .context("Failed to download asset index")?;
+        }
+
+        // Parse asset index
+        let asset_index_content = fs::read_to_string(&asset_index_path)
+            .await
+            .context("Failed to read asset index")?;
+
+        let asset_manifest: AssetManifest = serde_json::from_str(&asset_index_content)
+            .context("Failed to parse asset index JSON")?;
+        Ok(asset_manifest)
+    }
+
+    /// Download a file, verifying its size and checksum
     async fn download_file_with_verification(
         &self,
         url: &str,
         path: &Path,
-        expected_sha1: &str,
+        checksum: Checksum<'_>,
         expected_size: u64,
     ) -> Result<()> {
         let response = self
@@ -549,8 +955,6 @@ impl FileManager {
             .await
             .with_context(|| format!("Failed to create file: {}", path.display()))?;
 
-        let mut hasher = Sha1::new();
-
         // Read the response body in chunks
         let bytes = response
             .bytes()
@@ -561,7 +965,6 @@ impl FileManager {
             .await
             .with_context(|| format!("Failed to write to file: {}", path.display()))?;
 
-        hasher.update(&bytes);
         let downloaded = bytes.len() as u64;
 
         file.flush()
@@ -576,16 +979,129 @@ impl FileManager {
             .into());
         }
 
-        // Verify SHA1
-        let actual_sha1 = format!("{:x}", hasher.finalize());
-        if actual_sha1 != expected_sha1 {
-            return Err(FileManagerError::validation_failed(format!(
-                "SHA1 mismatch: expected {expected_sha1}, got {actual_sha1}"
-            ))
-            .into());
+        checksum.verify(&bytes)
+    }
+
+    /// Re-hash the client JAR and classpath libraries, returning the names of any that are
+    /// missing or corrupt
+    pub async fn find_invalid_game_files(
+        &self,
+        version_info: &VersionInfo,
+        minecraft_dir: &MinecraftDir,
+    ) -> Result<Vec<String>> {
+        let mut invalid = Vec::new();
+
+        let jar_path = minecraft_dir.version_jar_path(&version_info.id);
+        if !self
+            .is_file_valid(&jar_path, &version_info.downloads.client.sha1)
+            .await?
+        {
+            invalid.push(format!("{}.jar", version_info.id));
         }
 
-        Ok(())
+        for library in &version_info.libraries {
+            if !library.should_use() || library.is_native_library() {
+                continue;
+            }
+            let Some(artifact) = &library.downloads.artifact else {
+                continue;
+            };
+
+            let path = minecraft_dir.library_path(&get_library_path(&library.name));
+            if !self.is_file_valid(&path, &artifact.sha1).await? {
+                invalid.push(library.name.clone());
+            }
+        }
+
+        Ok(invalid)
+    }
+
+    /// Estimate how many bytes preparing a version still has to download
+    ///
+    /// Files on disk with the expected size count as present. They aren't hashed, so
+    /// this is quick but misses corrupt files. Without a local asset index, every
+    /// asset is counted.
+    pub async fn pending_download_size(
+        version_info: &VersionInfo,
+        minecraft_dir: &MinecraftDir,
+    ) -> u64 {
+        let client = &version_info.downloads.client;
+        let mut pending = 0;
+        if !has_size(
+            &minecraft_dir.version_jar_path(&version_info.id),
+            client.size,
+        )
+        .await
+        {
+            pending += client.size;
+        }
+
+        for (name, download) in version_info.library_downloads() {
+            let path = minecraft_dir.library_path(&get_library_path(&name));
+            if !has_size(&path, download.size).await {
+                pending += download.size;
+            }
+        }
+
+        let asset_index = &version_info.asset_index;
+        let local_index = fs::read_to_string(minecraft_dir.asset_index_path(&asset_index.id))
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str::<AssetManifest>(&content).ok());
+        match local_index {
+            Some(index) => {
+                for object in index.objects.values() {
+                    if !has_size(&minecraft_dir.asset_path(&object.hash), object.size).await {
+                        pending += object.size;
+                    }
+                }
+            }
+            None => pending += asset_index.size + asset_index.total_size,
+        }
+        pending
+    }
+
+    /// SHA1s of the libraries and assets of an already-prepared version
+    ///
+    /// Preparing a newer version can trust these instead of re-hashing the files
+    /// the two versions share. Only local files are read.
+    pub async fn prepared_file_hashes(
+        version_id: &str,
+        minecraft_dir: &MinecraftDir,
+    ) -> Result<HashSet<String>> {
+        let version_info = Self::get_local_version_info(version_id, minecraft_dir).await?;
+
+        let mut hashes: HashSet<String> = version_info
+            .libraries
+            .iter()
+            .filter_map(|library| library.downloads.artifact.as_ref())
+            .map(|artifact| artifact.sha1.clone())
+            .collect();
+
+        let index_path = minecraft_dir.asset_index_path(&version_info.asset_index.id);
+        match fs::read_to_string(&index_path).await {
+            Ok(content) => {
+                let index: AssetManifest =
+                    serde_json::from_str(&content).context("Failed to parse asset index JSON")?;
+                hashes.extend(index.objects.into_values().map(|object| object.hash));
+            }
+            Err(e) => warn!("⚠ No asset index for {version_id} ({e}); assets will be verified"),
+        }
+
+        Ok(hashes)
+    }
+
+    /// Check a file is present: trusted by hash if `known`, otherwise re-hashed
+    async fn is_file_present(
+        &self,
+        path: &Path,
+        expected_sha1: &str,
+        known: &HashSet<String>,
+    ) -> Result<bool> {
+        if known.contains(expected_sha1) && path.is_file() {
+            return Ok(true);
+        }
+        self.is_file_valid(path, expected_sha1).await
     }
 
     /// Check if a file exists and has the correct SHA1 hash
@@ -598,11 +1114,7 @@ impl FileManager {
             return Ok(false);
         };
 
-        let mut hasher = Sha1::new();
-        hasher.update(&content);
-        let actual_sha1 = format!("{:x}", hasher.finalize());
-
-        Ok(actual_sha1 == expected_sha1)
+        Ok(Checksum::Sha1(expected_sha1).verify(&content).is_ok())
     }
 
     /// Extract native libraries from JAR files
@@ -714,16 +1226,7 @@ impl FileManager {
                 continue;
             }
 
-            if std::path::Path::new(name)
-                .extension()
-                .is_some_and(|ext| ext.eq_ignore_ascii_case("dylib"))
-                || std::path::Path::new(name)
-                    .extension()
-                    .is_some_and(|ext| ext.eq_ignore_ascii_case("so"))
-                || std::path::Path::new(name)
-                    .extension()
-                    .is_some_and(|ext| ext.eq_ignore_ascii_case("dll"))
-            {
+            if is_native_file(Path::new(name)) {
                 let target_path = natives_dir.join(name);
                 if !target_path.exists() {
                     debug!(
@@ -742,12 +1245,150 @@ impl FileManager {
         Ok(false)
     }
 
+    /// Make sure a version's natives are extracted before launch
+    ///
+    /// If the natives directory holds no platform libraries although the version has
+    /// native jars, they are re-extracted from the jars already in the library cache.
+    /// With `rebuild`, they are re-extracted even if the directory looks intact.
+    pub async fn ensure_natives(
+        &self,
+        version_info: &VersionInfo,
+        minecraft_dir: &MinecraftDir,
+        rebuild: bool,
+    ) -> Result<()> {
+        let native_jars = launcher::files::FileManager::native_jars(version_info, minecraft_dir);
+        let natives_dir = minecraft_dir.natives_dir(&version_info.id);
+        if native_jars.is_empty() || (!rebuild && contains_native_files(&natives_dir)) {
+            return Ok(());
+        }
+
+        if rebuild {
+            info!("Rebuilding natives for {}", version_info.id);
+        } else {
+            warn!("⚠ No natives found for {}; re-extracting", version_info.id);
+        }
+        for (jar_path, library) in &native_jars {
+            if !jar_path.is_file() {
+                return Err(GameError::preparation_failed(format!(
+                    "Native library {} is missing. Run 'Redstonium prepare {}' to download it.",
+                    library.name, version_info.id
+                ))
+                .into());
+            }
+        }
+        self.replace_natives(&native_jars, &natives_dir).await?;
+
+        if !contains_native_files(&natives_dir) {
+            return Err(GameError::preparation_failed(format!(
+                "No native libraries for this platform were extracted to {}",
+                natives_dir.display()
+            ))
+            .into());
+        }
+        info!("✓ Natives extracted");
+        Ok(())
+    }
+
+    /// The jars a version takes natives from on this platform, with their library paths
+    fn native_jars(
+        version_info: &VersionInfo,
+        minecraft_dir: &MinecraftDir,
+    ) -> Vec<(std::path::PathBuf, Library)> {
+        launcher::files::FileManager::filter_native_libraries_by_architecture(
+            &version_info.libraries,
+        )
+        .into_iter()
+        .filter(Library::should_use)
+        .filter_map(|library| {
+            let name = if library.is_native_library() {
+                library.downloads.artifact.as_ref()?;
+                library.name.clone()
+            } else {
+                let classifier = library.get_native_classifier()?;
+                library.downloads.classifiers.as_ref()?.get(&classifier)?;
+                format!("{}:{classifier}", library.name)
+            };
+            Some((
+                minecraft_dir.library_path(&get_library_path(&name)),
+                library,
+            ))
+        })
+        .collect()
+    }
+
+    /// Whether any native jar has files missing from the natives directory
+    fn natives_outdated(
+        native_jars: &[(std::path::PathBuf, Library)],
+        natives_dir: &Path,
+    ) -> Result<bool> {
+        for (jar_path, library) in native_jars {
+            if launcher::files::FileManager::should_extract_natives(jar_path, natives_dir, library)?
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Extract every native jar into a staging directory, then swap it in for the natives
+    ///
+    /// If any jar fails, the previous natives (or none) stay in place instead of a partial
+    /// set that looks complete but is missing a library.
+    async fn replace_natives(
+        &self,
+        native_jars: &[(std::path::PathBuf, Library)],
+        natives_dir: &Path,
+    ) -> Result<()> {
+        let staging = natives_dir.with_file_name("natives.partial");
+        let previous = natives_dir.with_file_name("natives.old");
+        for leftover in [&staging, &previous] {
+            if leftover.exists() {
+                fs::remove_dir_all(leftover)
+                    .await
+                    .with_context(|| format!("Failed to remove leftover {}", leftover.display()))?;
+            }
+        }
+
+        for (jar_path, library) in native_jars {
+            let extracted = self
+                .extract_natives(jar_path, &staging, library)
+                .await
+                .with_context(|| format!("Failed to extract natives from {}", library.name));
+            if let Err(e) = extracted {
+                let _ = fs::remove_dir_all(&staging).await;
+                return Err(e);
+            }
+        }
+
+        if natives_dir.exists() {
+            fs::rename(natives_dir, &previous).await.with_context(|| {
+                format!(
+                    "Failed to move aside natives directory {}",
+                    natives_dir.display()
+                )
+            })?;
+        }
+        if let Err(e) = fs::rename(&staging, natives_dir).await {
+            // Put the previous natives back rather than leaving none
+            let _ = fs::rename(&previous, natives_dir).await;
+            let _ = fs::remove_dir_all(&staging).await;
+            return Err(e).with_context(|| {
+                format!(
+                    "Failed to replace natives directory {}",
+                    natives_dir.display()
+                )
+            });
+        }
+        let _ = fs::remove_dir_all(&previous).await;
+        Ok(())
+    }
+
     #[allow(clippy::match_same_arms)]
     /// Filter native libraries to prefer the best architecture match
     fn filter_native_libraries_by_architecture(libraries: &[Library]) -> Vec<Library> {
         let mut filtered = Vec::new();
 
-        let current_arch = match std::env::consts::ARCH {
+        let current_arch = match crate::launcher::version::target_arch() {
             "aarch64" => "arm64",
             "x86_64" => "x64",
             _ => "x64", // Default to x64 for unknown architectures
@@ -764,7 +1405,7 @@ impl FileManager {
             if library.is_native_library() {
                 debug!("Found native library: {}", library.name);
 
-                // Extract base name by removing architecture-specific suffixes
+                // Extract base name by removing architecture- and libc-specific suffixes
                 let base_name = if library.name.contains("-arm64") {
                     library.name.replace("-arm64", "")
                 } else if library.name.contains("-x64") {
@@ -772,7 +1413,8 @@ impl FileManager {
                 } else {
                     // Library without specific architecture suffix
                     library.name.clone()
-                };
+                }
+                .replace("-musl", "");
 
                 debug!("  Base name: {base_name}");
                 grouped_libraries
@@ -791,8 +1433,13 @@ impl FileManager {
             grouped_libraries.len()
         );
 
-        // For each group of native libraries, prefer the architecture-specific one
-        for (base_name, group) in &grouped_libraries {
+        // For each group of native libraries, prefer the architecture-specific one,
+        // trying variants built for the system's libc first
+        let musl = crate::launcher::version::is_musl();
+        for (base_name, group) in &mut grouped_libraries {
+            group.sort_by_key(|lib| lib.name.contains("-musl") != musl);
+            let group = &*group;
+
             debug!(
                 "Processing group '{}' with {} variants:",
                 base_name,
@@ -847,12 +1494,49 @@ impl FileManager {
     }
 }
 
+/// Whether a file is a platform native library (`.dll`, `.so` or `.dylib`)
+fn is_native_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        ["dll", "so", "dylib"]
+            .iter()
+            .any(|native| ext.eq_ignore_ascii_case(native))
+    })
+}
+
+/// Whether a file exists with exactly this size
+async fn has_size(path: &Path, size: u64) -> bool {
+    fs::metadata(path)
+        .await
+        .is_ok_and(|metadata| metadata.len() == size)
+}
+
+/// Whether a directory, or any directory below it, contains a native library
+fn contains_native_files(dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            contains_native_files(&path)
+        } else {
+            is_native_file(&path)
+        }
+    })
+}
+
 /// Helper function to convert library name to file path
 /// Example: org.lwjgl:lwjgl:3.3.3 -> org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3.jar
 /// Example: org.lwjgl:lwjgl-opengl:3.3.3:natives-macos-arm64 -> org/lwjgl/lwjgl-opengl/3.3.3/lwjgl-opengl-3.3.3-natives-macos-arm64.jar
+/// Example: net.minecraftforge:forge:1.20.1-47.2.0:universal@zip -> net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-universal.zip
 pub fn get_library_path(library_name: &str) -> String {
+    // A trailing "@ext" overrides the default .jar extension
+    let (coordinates, extension) = library_name
+        .rsplit_once('@')
+        .unwrap_or((library_name, "jar"));
+
     // Parse library name like "com.mojang:brigadier:1.0.18" or "org.lwjgl:lwjgl-freetype:3.3.3:natives-macos-arm64"
-    let parts: Vec<&str> = library_name.split(':').collect();
+    let parts: Vec<&str> = coordinates.split(':').collect();
     if parts.len() >= 4 {
         // Handle classifier format (e.g., org.lwjgl:lwjgl-freetype:3.3.3:natives-macos-arm64)
         let group = parts[0].replace('.', "/");
@@ -860,17 +1544,17 @@ pub fn get_library_path(library_name: &str) -> String {
         let version = parts[2];
         let classifier = parts[3];
 
-        format!("{group}/{name}/{version}/{name}-{version}-{classifier}.jar")
+        format!("{group}/{name}/{version}/{name}-{version}-{classifier}.{extension}")
     } else if parts.len() >= 3 {
         // Standard format (e.g., com.mojang:brigadier:1.0.18)
         let group = parts[0].replace('.', "/");
         let name = parts[1];
         let version = parts[2];
 
-        format!("{group}/{name}/{version}/{name}-{version}.jar")
+        format!("{group}/{name}/{version}/{name}-{version}.{extension}")
     } else {
         // Fallback for malformed library names
-        format!("{}.jar", library_name.replace(':', "/"))
+        format!("{}.{extension}", coordinates.replace(':', "/"))
     }
 }
 
@@ -878,8 +1562,453 @@ pub fn get_library_path(library_name: &str) -> String {
 // futures-util = "0.3"
 // sha1 = "0.10"
 
+/// Chooses how many assets to download at once
+///
+/// A configured size stays fixed. Otherwise it starts conservative, doubles after
+/// each clean batch and halves when more than a tenth of a batch fails. When
+/// auto-tuning, it instead doubles only while throughput keeps improving, then
+/// settles on the fastest size seen.
+struct AssetBatchSizer {
+    size: usize,
+    mode: BatchMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BatchMode {
+    Fixed,
+    Adaptive,
+    /// Measuring throughput; holds the fastest size and its bytes per second so far
+    Tuning(Option<(usize, f64)>),
+    /// Tuning finished; the size only shrinks on failures
+    Tuned,
+}
+
+/// Throughput must improve by this factor for a larger batch to count as faster
+const TUNING_MIN_GAIN: f64 = 1.1;
+
+impl AssetBatchSizer {
+    fn new(configured: Option<usize>) -> Self {
+        match configured {
+            Some(size) => Self {
+                size,
+                mode: BatchMode::Fixed,
+            },
+            None => Self {
+                size: INITIAL_ASSET_BATCH,
+                mode: BatchMode::Adaptive,
+            },
+        }
+    }
+
+    /// Start small and pick the batch size from measured throughput
+    fn auto_tuned() -> Self {
+        Self {
+            size: MIN_ASSET_BATCH,
+            mode: BatchMode::Tuning(None),
+        }
+    }
+
+    fn record(&mut self, batch_len: usize, failures: usize) {
+        if self.mode == BatchMode::Fixed {
+            return;
+        }
+
+        if failures == 0 {
+            if self.mode == BatchMode::Adaptive {
+                self.size = (self.size * 2).min(MAX_ASSET_BATCH);
+            }
+        } else if failures * 10 > batch_len {
+            self.size = (self.size / 2).max(MIN_ASSET_BATCH);
+            if matches!(self.mode, BatchMode::Tuning(_)) {
+                self.mode = BatchMode::Tuned;
+            }
+            debug!(
+                "{failures}/{batch_len} asset downloads failed, reducing batch size to {}",
+                self.size
+            );
+        }
+    }
+
+    /// Feed a batch's downloaded bytes and duration to the tuner
+    ///
+    /// Batches that downloaded nothing (all assets already present) say nothing
+    /// about the connection and are ignored.
+    fn record_throughput(&mut self, bytes: u64, elapsed: std::time::Duration) {
+        let BatchMode::Tuning(best) = self.mode else {
+            return;
+        };
+        if bytes == 0 || elapsed.is_zero() {
+            return;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let rate = bytes as f64 / elapsed.as_secs_f64();
+        debug!(
+            "{} assets at a time: {:.1} MiB/s",
+            self.size,
+            rate / (1024.0 * 1024.0)
+        );
+        match best {
+            Some((best_size, best_rate)) if rate < best_rate * TUNING_MIN_GAIN => {
+                self.size = best_size;
+            }
+            _ if self.size >= MAX_ASSET_BATCH => {}
+            _ => {
+                self.mode = BatchMode::Tuning(Some((self.size, rate)));
+                self.size = (self.size * 2).min(MAX_ASSET_BATCH);
+                return;
+            }
+        }
+        self.mode = BatchMode::Tuned;
+        info!("Auto-tuned asset downloads to {} at a time", self.size);
+    }
+}
+
 impl Default for FileManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_asset_batch_sizer_adapts() {
+        let mut sizer = AssetBatchSizer::new(None);
+        assert_eq!(sizer.size, INITIAL_ASSET_BATCH);
+
+        sizer.record(32, 0);
+        assert_eq!(sizer.size, 64);
+        sizer.record(64, 1);
+        assert_eq!(sizer.size, 64);
+        sizer.record(64, 20);
+        assert_eq!(sizer.size, 32);
+
+        for _ in 0..10 {
+            sizer.record(sizer.size, sizer.size);
+        }
+        assert_eq!(sizer.size, MIN_ASSET_BATCH);
+        for _ in 0..10 {
+            sizer.record(sizer.size, 0);
+        }
+        assert_eq!(sizer.size, MAX_ASSET_BATCH);
+
+        let mut fixed = AssetBatchSizer::new(Some(50));
+        fixed.record(50, 50);
+        assert_eq!(fixed.size, 50);
+    }
+
+    #[test]
+    fn test_asset_batch_sizer_auto_tunes_to_fastest() {
+        let second = std::time::Duration::from_secs(1);
+        let mut sizer = AssetBatchSizer::auto_tuned();
+        assert_eq!(sizer.size, MIN_ASSET_BATCH);
+
+        // Clean batches don't grow it on their own
+        sizer.record(MIN_ASSET_BATCH, 0);
+        assert_eq!(sizer.size, MIN_ASSET_BATCH);
+
+        sizer.record_throughput(1_000_000, second);
+        assert_eq!(sizer.size, MIN_ASSET_BATCH * 2);
+        sizer.record_throughput(3_000_000, second);
+        assert_eq!(sizer.size, MIN_ASSET_BATCH * 4);
+        // No real gain at the larger size: settle on the previous one
+        sizer.record_throughput(3_100_000, second);
+        assert_eq!(sizer.size, MIN_ASSET_BATCH * 2);
+
+        sizer.record_throughput(9_000_000, second);
+        assert_eq!(sizer.size, MIN_ASSET_BATCH * 2);
+    }
+
+    #[test]
+    fn test_get_library_path() {
+        assert_eq!(
+            get_library_path("com.mojang:brigadier:1.0.18"),
+            "com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar"
+        );
+        assert_eq!(
+            get_library_path("org.lwjgl:lwjgl:3.3.3:natives-linux"),
+            "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar"
+        );
+        assert_eq!(
+            get_library_path("net.minecraftforge:forge:1.20.1-47.2.0:universal@zip"),
+            "net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-universal.zip"
+        );
+        assert_eq!(
+            get_library_path("de.oceanlabs.mcp:mcp_config:1.20.1-20230612.114412@zip"),
+            "de/oceanlabs/mcp/mcp_config/1.20.1-20230612.114412/mcp_config-1.20.1-20230612.114412.zip"
+        );
+    }
+
+    /// A scratch Minecraft directory unique to one test
+    fn test_minecraft_dir(name: &str) -> MinecraftDir {
+        let base_path =
+            std::env::temp_dir().join(format!("redstonium-files-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base_path);
+        MinecraftDir::at(base_path).unwrap()
+    }
+
+    fn sha1_hex(data: &[u8]) -> String {
+        format!("{:x}", Sha1::digest(data))
+    }
+
+    /// Minimal version JSON whose asset index lives on `server`
+    fn version_json(server: &str, asset_index: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "1.20.1",
+            "type": "release",
+            "time": "2023-06-12T13:25:51+00:00",
+            "releaseTime": "2023-06-12T13:25:51+00:00",
+            "downloads": {"client": {"sha1": "00", "size": 0, "url": format!("{server}/client.jar")}},
+            "libraries": [],
+            "mainClass": "net.minecraft.client.main.Main",
+            "assetIndex": {
+                "id": "5",
+                "sha1": sha1_hex(asset_index.as_bytes()),
+                "size": asset_index.len(),
+                "totalSize": 10,
+                "url": format!("{server}/indexes/5.json")
+            },
+            "assets": "5"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_version_info_from_mock_server() {
+        let server = MockServer::start().await;
+        let manifest = serde_json::json!({
+            "latest": {"release": "1.20.1", "snapshot": "1.20.1"},
+            "versions": [{
+                "id": "1.20.1",
+                "type": "release",
+                "url": format!("{}/v1/1.20.1.json", server.uri()),
+                "time": "2023-06-12T13:25:51+00:00",
+                "releaseTime": "2023-06-12T13:25:51+00:00"
+            }]
+        });
+        Mock::given(method("GET"))
+            .and(path("/manifest.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(manifest))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/1.20.1.json"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(version_json(&server.uri(), "{}")),
+            )
+            .mount(&server)
+            .await;
+
+        let file_manager = FileManager::builder()
+            .manifest_url(format!("{}/manifest.json", server.uri()))
+            .resources_url(server.uri())
+            .build();
+        let version_info = file_manager.get_version_info("1.20.1").await.unwrap();
+        assert_eq!(version_info.id, "1.20.1");
+        assert_eq!(version_info.main_class, "net.minecraft.client.main.Main");
+
+        // The manifest is fetched once and reused
+        assert!(file_manager.get_version_info("1.99").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_version_info_refetches_on_hash_mismatch() {
+        let server = MockServer::start().await;
+        let remote = version_json(&server.uri(), "{}").to_string();
+        let manifest = serde_json::json!({
+            "latest": {"release": "1.20.1", "snapshot": "1.20.1"},
+            "versions": [{
+                "id": "1.20.1",
+                "type": "release",
+                "url": format!("{}/v1/1.20.1.json", server.uri()),
+                "time": "2023-06-12T13:25:51+00:00",
+                "releaseTime": "2023-06-12T13:25:51+00:00",
+                "sha1": sha1_hex(remote.as_bytes())
+            }]
+        });
+        Mock::given(method("GET"))
+            .and(path("/manifest.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(manifest))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/1.20.1.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(remote.clone()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let minecraft_dir = test_minecraft_dir("stale-json");
+        minecraft_dir.ensure_version_dir("1.20.1").unwrap();
+        let mut stale = version_json(&server.uri(), "{}");
+        stale["mainClass"] = serde_json::json!("net.minecraft.Stale");
+        std::fs::write(minecraft_dir.version_json_path("1.20.1"), stale.to_string()).unwrap();
+
+        let file_manager = FileManager::builder()
+            .manifest_url(format!("{}/manifest.json", server.uri()))
+            .build();
+        let version_info = file_manager
+            .get_version_info_cached("1.20.1", &minecraft_dir)
+            .await
+            .unwrap();
+        assert_eq!(version_info.main_class, "net.minecraft.client.main.Main");
+
+        // A copy matching the manifest's hash is used without fetching it again
+        std::fs::write(minecraft_dir.version_json_path("1.20.1"), &remote).unwrap();
+        let version_info = file_manager
+            .get_version_info_cached("1.20.1", &minecraft_dir)
+            .await
+            .unwrap();
+        assert_eq!(version_info.main_class, "net.minecraft.client.main.Main");
+    }
+
+    #[test]
+    fn test_checksum_parse_and_verify() {
+        let sha256 = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(Checksum::parse(sha256).unwrap().verify(b"hello").is_ok());
+        let mismatch = Checksum::parse(sha256).unwrap().verify(b"world");
+        assert!(
+            mismatch
+                .unwrap_err()
+                .to_string()
+                .contains("SHA256 mismatch")
+        );
+        assert!(Checksum::parse("md5:00").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_download_rejects_sha1_and_size_mismatch() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .mount(&server)
+            .await;
+        let dir = test_minecraft_dir("mismatch");
+        let target = dir.base_path.join("file");
+        let url = format!("{}/file", server.uri());
+        let file_manager = FileManager::new();
+
+        let sha1_error = file_manager
+            .download_file_with_verification(&url, &target, Checksum::Sha1(&sha1_hex(b"world")), 5)
+            .await
+            .unwrap_err();
+        assert!(sha1_error.to_string().contains("SHA1 mismatch"));
+
+        let size_error = file_manager
+            .download_file_with_verification(&url, &target, Checksum::Sha1(&sha1_hex(b"hello")), 6)
+            .await
+            .unwrap_err();
+        assert!(size_error.to_string().contains("Size mismatch"));
+
+        file_manager
+            .download_file_with_verification(&url, &target, Checksum::Sha1(&sha1_hex(b"hello")), 5)
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir.base_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_assets_skips_valid_files() {
+        let server = MockServer::start().await;
+        let (hello, world) = (sha1_hex(b"hello"), sha1_hex(b"world"));
+        let asset_index = serde_json::json!({"objects": {
+            "present.txt": {"hash": hello, "size": 5},
+            "missing.txt": {"hash": world, "size": 5}
+        }})
+        .to_string();
+
+        Mock::given(method("GET"))
+            .and(path("/indexes/5.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(asset_index.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}/{hello}", &hello[..2])))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}/{world}", &world[..2])))
+            .respond_with(ResponseTemplate::new(200).set_body_string("world"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = test_minecraft_dir("assets");
+        let present = dir.asset_path(&hello);
+        std::fs::create_dir_all(present.parent().unwrap()).unwrap();
+        std::fs::write(&present, "hello").unwrap();
+
+        let version_info: VersionInfo =
+            serde_json::from_value(version_json(&server.uri(), &asset_index)).unwrap();
+        let file_manager = FileManager::builder()
+            .manifest_url(format!("{}/manifest.json", server.uri()))
+            .resources_url(server.uri())
+            .build();
+        file_manager
+            .download_assets(&version_info, &dir, &HashSet::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.asset_path(&world)).unwrap(),
+            "world"
+        );
+        std::fs::remove_dir_all(&dir.base_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_natives_extraction_keeps_previous_natives() {
+        let dir = test_minecraft_dir("natives");
+        let natives_dir = dir.natives_dir("1.12.2");
+        std::fs::create_dir_all(&natives_dir).unwrap();
+        std::fs::write(natives_dir.join("liblwjgl.so"), b"old").unwrap();
+
+        let good_jar = dir.base_path.join("good.jar");
+        let mut writer = zip::ZipWriter::new(File::create(&good_jar).unwrap());
+        writer
+            .start_file("liblwjgl.so", zip::write::FileOptions::default())
+            .unwrap();
+        std::io::Write::write_all(&mut writer, b"new").unwrap();
+        writer.finish().unwrap();
+
+        let library: Library =
+            serde_json::from_value(serde_json::json!({"name": "org.lwjgl:lwjgl", "downloads": {}}))
+                .unwrap();
+        let missing_jar = dir.base_path.join("missing.jar");
+        let file_manager = FileManager::new();
+
+        let jars = [
+            (good_jar.clone(), library.clone()),
+            (missing_jar, library.clone()),
+        ];
+        assert!(
+            file_manager
+                .replace_natives(&jars, &natives_dir)
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            std::fs::read(natives_dir.join("liblwjgl.so")).unwrap(),
+            b"old"
+        );
+        assert!(!natives_dir.with_file_name("natives.partial").exists());
+
+        file_manager
+            .replace_natives(&[(good_jar, library)], &natives_dir)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read(natives_dir.join("liblwjgl.so")).unwrap(),
+            b"new"
+        );
+        std::fs::remove_dir_all(&dir.base_path).unwrap();
+    }
+}
@@ -1,30 +1,599 @@
 use crate::error::{GameError, Result, ResultExt};
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::auth::AuthResult;
+use crate::launcher::config::LauncherConfig;
 use crate::launcher::instance::InstanceConfig;
 use crate::launcher::java::JavaManager;
 use crate::launcher::minecraft_dir::MinecraftDir;
-use crate::launcher::version::{ArgumentValue, ArgumentValueType, VersionInfo};
+use crate::launcher::version::{
+    ArgumentValue, ArgumentValueType, Library, VersionInfo, compare_library_versions,
+};
 use crate::launcher::{self, get_library_path};
 
+/// Longest command line we pass directly; beyond this the classpath goes in an argfile.
+/// Windows caps the whole command line at 32767 characters, Linux caps a single argument
+/// at 128 KiB. Headroom is left for the game arguments added after the classpath.
+const MAX_COMMAND_LINE: usize = if cfg!(windows) { 32_767 } else { 131_072 };
+const GAME_ARGS_HEADROOM: usize = 4096;
+
+/// File in an instance's game directory holding the PID of the game running it
+const LAUNCH_LOCK_FILE: &str = ".launch.lock";
+
+/// Per-launch choices that affect the game command
+#[derive(Debug, Clone)]
+pub struct LaunchSettings {
+    /// Launcher name reported to the game
+    pub brand: String,
+    /// Print the command instead of starting the game
+    pub dry_run: bool,
+    /// Write the command to this file as a JSON array instead of starting the game
+    pub emit_command: Option<PathBuf>,
+    /// Write a standalone launch script to this file instead of starting the game
+    pub export_script: Option<PathBuf>,
+    /// Singleplayer world to boot straight into via quick play
+    pub world: Option<String>,
+    /// Run the game in demo mode instead of the full game; enables the `is_demo_user` feature
+    pub demo: bool,
+    /// Multiplayer server to join on startup
+    pub server: Option<ServerAddress>,
+    /// Back up the instance's worlds before starting the game
+    pub backup_saves: bool,
+    /// Kill the game if it is still running after this long
+    pub launch_timeout: Option<std::time::Duration>,
+    /// Require the exact Java major version instead of falling back to a newer one
+    pub strict_java: bool,
+    /// Launch even if the instance's launch lock names a running process
+    pub force: bool,
+}
+
+impl Default for LaunchSettings {
+    fn default() -> Self {
+        Self {
+            brand: launcher::LAUNCHER_BRAND.to_string(),
+            dry_run: false,
+            emit_command: None,
+            export_script: None,
+            world: None,
+            demo: false,
+            server: None,
+            backup_saves: false,
+            launch_timeout: None,
+            strict_java: false,
+            force: false,
+        }
+    }
+}
+
+impl LaunchSettings {
+    /// Whether this launch runs the game rather than only producing its command
+    pub fn starts_game(&self) -> bool {
+        !self.dry_run && self.emit_command.is_none() && self.export_script.is_none()
+    }
+
+    /// Whether a `features` condition in an argument rule is enabled for this launch
+    fn feature_enabled(&self, feature: &str) -> bool {
+        match feature {
+            "is_demo_user" => self.demo,
+            "is_quick_play_singleplayer" => self.world.is_some(),
+            "is_quick_play_multiplayer" => self.server.is_some(),
+            _ => false,
+        }
+    }
+}
+
+/// A multiplayer server address with its port
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerAddress {
+    pub host: String,
+    pub port: u16,
+}
+
+impl ServerAddress {
+    const DEFAULT_PORT: u16 = 25565;
+
+    /// Parse `host`, `host:port`, `[ipv6]` or `[ipv6]:port`
+    pub fn parse(address: &str) -> Result<Self> {
+        let invalid = || GameError::launch_failed(format!("Invalid server address: '{address}'"));
+
+        let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+            let (host, rest) = rest.split_once(']').ok_or_else(invalid)?;
+            let port = match rest.strip_prefix(':') {
+                Some(port) => Some(port),
+                None if rest.is_empty() => None,
+                None => return Err(invalid().into()),
+            };
+            (host, port)
+        } else {
+            match address.rsplit_once(':') {
+                Some((host, port)) if !host.contains(':') => (host, Some(port)),
+                // No port given, or a bare IPv6 address which can't carry one
+                _ => (address, None),
+            }
+        };
+
+        if host.is_empty() {
+            return Err(invalid().into());
+        }
+        let port = match port {
+            Some(port) => port.parse().map_err(|_| invalid())?,
+            None => Self::DEFAULT_PORT,
+        };
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+impl std::fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Marks an instance as running, so a second launch can't write to the same worlds
+///
+/// Holds the launcher's PID until the game starts, then the game's, and is removed
+/// when dropped.
+struct LaunchLock {
+    path: PathBuf,
+    pid: u32,
+}
+
+impl LaunchLock {
+    /// Claim `game_dir` for this launch before anything touches its worlds
+    ///
+    /// The lock file is created atomically, so of two simultaneous launches only one
+    /// gets it. A lock naming a process that has exited is replaced; one naming a live
+    /// process only with `force`. Returns `None` if the lock can't be written at all,
+    /// which only loses the protection.
+    fn acquire(game_dir: &Path, force: bool) -> Result<Option<Self>> {
+        let path = game_dir.join(LAUNCH_LOCK_FILE);
+        let pid = std::process::id();
+
+        match create_lock_file(&path, pid) {
+            Ok(()) => return Ok(Some(Self { path, pid })),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                warn_lock_unwritable(&path, &e);
+                return Ok(None);
+            }
+        }
+
+        match lock_holder(&path) {
+            Some(holder) if process_alive(holder) && force => {
+                warn!("⚠ Instance seems to be running already (PID {holder}), launching anyway");
+                let lock = Self { path, pid };
+                lock.write(pid);
+                return Ok(Some(lock));
+            }
+            Some(holder) if process_alive(holder) => return Err(already_running(holder)),
+            _ => debug!("Replacing stale launch lock {}", path.display()),
+        }
+
+        // Another launch may replace the same stale lock first; only one create succeeds
+        let _ = std::fs::remove_file(&path);
+        match create_lock_file(&path, pid) {
+            Ok(()) => Ok(Some(Self { path, pid })),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && force => {
+                let lock = Self { path, pid };
+                lock.write(pid);
+                Ok(Some(lock))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                Err(already_running(lock_holder(&path).unwrap_or_default()))
+            }
+            Err(e) => {
+                warn_lock_unwritable(&path, &e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Record `pid` as the lock's holder, e.g. the game once it has started
+    fn write(&self, pid: u32) {
+        if let Err(e) = std::fs::write(&self.path, pid.to_string()) {
+            warn!("⚠ Failed to write launch lock {}: {e}", self.path.display());
+        }
+    }
+
+    /// Hand the lock over to the started game process
+    fn hand_over(&mut self, pid: u32) {
+        self.write(pid);
+        self.pid = pid;
+    }
+}
+
+impl Drop for LaunchLock {
+    fn drop(&mut self) {
+        // A forced second launch may have replaced the lock with its own
+        let still_ours = std::fs::read_to_string(&self.path)
+            .is_ok_and(|content| content.trim() == self.pid.to_string());
+        if still_ours {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Create a launch lock file holding `pid`, failing if it already exists
+fn create_lock_file(path: &Path, pid: u32) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?;
+    file.write_all(pid.to_string().as_bytes())
+}
+
+/// A lock that can't be written only loses the protection, so launching goes ahead
+fn warn_lock_unwritable(path: &Path, error: &std::io::Error) {
+    warn!("⚠ Failed to write launch lock {}: {error}", path.display());
+}
+
+/// The error for a launch lock held by a running process
+fn already_running(pid: u32) -> crate::error::RustifiedError {
+    GameError::already_running(format!(
+        "This instance is already running (PID {pid}); launching it twice can corrupt its worlds. Close it first, or use --force if PID {pid} is not Minecraft."
+    ))
+    .into()
+}
+
+/// PID recorded in a launch lock file, if it exists and holds one
+fn lock_holder(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.trim().parse::<u32>().ok())
+}
+
+/// Whether a process with this PID is running
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    use nix::errno::Errno;
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    // Signal 0 only checks; EPERM means it exists but belongs to another user
+    i32::try_from(pid)
+        .is_ok_and(|pid| matches!(kill(Pid::from_raw(pid), None), Ok(()) | Err(Errno::EPERM)))
+}
+
+/// Whether a process with this PID is running
+#[cfg(not(unix))]
+fn process_alive(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
+
 pub struct GameLauncher {}
 
 impl GameLauncher {
     /// Launch the Minecraft game with a specific instance
-    pub fn launch(
+    ///
+    /// With `settings.dry_run`, the resolved command is printed instead of being run;
+    /// with `settings.emit_command` or `settings.export_script`, it is written to a file instead.
+    /// Returns the game's exit code; a crash is not a launcher error.
+    pub async fn launch(
         version_info: &VersionInfo,
         auth: &AuthResult,
         minecraft_dir: &MinecraftDir,
         java_manager: &JavaManager,
+        config: &LauncherConfig,
         instance: Option<&InstanceConfig>,
-    ) -> Result<()> {
+        settings: &LaunchSettings,
+    ) -> Result<i32> {
         info!("Launching Minecraft {}", version_info.id);
 
+        if launcher::version::is_musl() {
+            warn!(
+                "⚠ musl libc detected: Minecraft's native libraries are built for glibc and may fail to load"
+            );
+            info!(
+                "💡 Install a glibc compatibility layer (e.g. gcompat) or use a glibc-based system"
+            );
+        }
+
+        let mut cmd = launcher::game::GameLauncher::build_command(
+            version_info,
+            auth,
+            minecraft_dir,
+            java_manager,
+            config,
+            instance,
+            settings,
+        )?;
+        let command_line = launcher::game::GameLauncher::format_command(&cmd, auth);
+
+        if let Some(path) = &settings.emit_command {
+            launcher::game::GameLauncher::write_command_file(&cmd, auth, path)?;
+            info!("✓ Launch command written to {}", path.display());
+            return Ok(0);
+        }
+
+        if let Some(path) = &settings.export_script {
+            launcher::script::write_launch_script(&cmd, &auth.access_token, path)?;
+            info!("✓ Launch script written to {}", path.display());
+            info!(
+                "💡 The access token expires; the script reads {} and asks for it when unset",
+                launcher::script::ACCESS_TOKEN_VAR
+            );
+            return Ok(0);
+        }
+
+        if settings.dry_run {
+            info!("Dry run: Minecraft would be started with:");
+            info!("{command_line}");
+            if let Some(dir) = cmd.get_current_dir() {
+                info!("Working directory: {}", dir.display());
+            }
+            return Ok(0);
+        }
+
+        launcher::game::GameLauncher::check_display()?;
+
+        info!("Starting Minecraft process...");
+        info!("Java command: {command_line}");
+        let mut lock = None;
+        if let (Some(inst), Some(dir)) = (instance, cmd.get_current_dir()) {
+            lock = LaunchLock::acquire(dir, settings.force)?;
+            info!("Game directory: {}", dir.display());
+            if settings.backup_saves {
+                let keep = inst.settings.backup_limit();
+                // A failed backup must never stop the game from starting
+                match launcher::game::GameLauncher::backup_saves(dir, keep) {
+                    Ok(Some(path)) => info!("✓ Worlds backed up to {}", path.display()),
+                    Ok(None) => debug!("No worlds to back up"),
+                    Err(e) => warn!("⚠ World backup failed, launching anyway: {e}"),
+                }
+            }
+        }
+
+        // Launch the game
+        let started_at = std::time::SystemTime::now();
+        let mut child = cmd.spawn().context("Failed to start Minecraft process")?;
+
+        info!("Minecraft process started with PID: {}", child.id());
+        if let Some(lock) = &mut lock {
+            lock.hand_over(child.id());
+        }
+
+        // Wait for the process to complete
+        let status =
+            launcher::game::GameLauncher::wait_for_exit(child, settings.launch_timeout).await?;
+
+        if status.success() {
+            info!("Minecraft exited successfully");
+        } else if let Some(dir) = cmd.get_current_dir() {
+            launcher::game::GameLauncher::report_latest_crash(dir, started_at);
+        }
+
+        Ok(launcher::game::GameLauncher::exit_code(status))
+    }
+
+    /// Fail early on Linux without a graphical session, where LWJGL would crash opaquely
+    fn check_display() -> Result<()> {
+        if !cfg!(target_os = "linux") {
+            return Ok(());
+        }
+
+        let has_display = ["DISPLAY", "WAYLAND_DISPLAY"]
+            .iter()
+            .any(|var| env::var_os(var).is_some_and(|value| !value.is_empty()));
+        if has_display {
+            return Ok(());
+        }
+
+        info!("💡 Run from a desktop session, or use a virtual display such as xvfb-run");
+        Err(GameError::display_unavailable(
+            "no display found (DISPLAY and WAYLAND_DISPLAY are unset); the Minecraft client requires a graphical session",
+        )
+        .into())
+    }
+
+    /// Wait for the game to exit, forwarding Ctrl-C and SIGTERM so it can save and quit
+    ///
+    /// The launcher keeps waiting after forwarding a signal; a second one kills the game.
+    /// With a `timeout`, the game is killed once it has run that long.
+    async fn wait_for_exit(
+        mut child: std::process::Child,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<std::process::ExitStatus> {
+        const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+        let deadline = timeout.map(|timeout| tokio::time::Instant::now() + timeout);
+        let shutdown = launcher::game::GameLauncher::shutdown_requested();
+        tokio::pin!(shutdown);
+        let mut signals_received = 0;
+
+        loop {
+            if let Some(status) = child
+                .try_wait()
+                .context("Failed to wait for Minecraft process")?
+            {
+                return Ok(status);
+            }
+
+            if let (Some(deadline), Some(timeout)) = (deadline, timeout)
+                && tokio::time::Instant::now() >= deadline
+            {
+                child.kill().context("Failed to kill Minecraft process")?;
+                child
+                    .wait()
+                    .context("Failed to wait for Minecraft process")?;
+                return Err(GameError::launch_timed_out(format!(
+                    "Minecraft was still running after {}s and was killed",
+                    timeout.as_secs()
+                ))
+                .into());
+            }
+
+            tokio::select! {
+                () = tokio::time::sleep(POLL_INTERVAL) => {}
+                () = &mut shutdown => {
+                    signals_received += 1;
+                    launcher::game::GameLauncher::forward_shutdown(&mut child, signals_received > 1);
+                    shutdown.set(launcher::game::GameLauncher::shutdown_requested());
+                }
+            }
+        }
+    }
+
+    /// Resolve when the launcher is asked to stop: Ctrl-C, or SIGTERM on Unix
+    async fn shutdown_requested() {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+            if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+        }
+
+        if tokio::signal::ctrl_c().await.is_err() {
+            // Without a handler there is nothing to forward; just wait for the game
+            std::future::pending::<()>().await;
+        }
+    }
+
+    /// Ask the game to save and exit, or kill it when `force` is set
+    fn forward_shutdown(child: &mut std::process::Child, force: bool) {
+        let pid = child.id();
+        if force {
+            match child.kill() {
+                Ok(()) => warn!("⚠ Killed Minecraft (PID {pid})"),
+                Err(e) => warn!("⚠ Failed to kill Minecraft (PID {pid}): {e}"),
+            }
+            return;
+        }
+
+        // The console delivers Ctrl-C to the game as well; SIGTERM must be passed on
+        #[cfg(unix)]
+        {
+            use nix::sys::signal::{Signal, kill};
+            use nix::unistd::Pid;
+
+            if let Ok(raw_pid) = i32::try_from(pid)
+                && let Err(e) = kill(Pid::from_raw(raw_pid), Signal::SIGTERM)
+            {
+                warn!("⚠ Failed to signal Minecraft (PID {pid}): {e}");
+            }
+        }
+        info!("Waiting for Minecraft to save and exit; press Ctrl-C again to kill it");
+    }
+
+    /// Zip the game directory's `saves` into `backups/`, keeping the newest `keep` archives
+    ///
+    /// Returns the new archive, or `None` when there are no worlds to back up.
+    fn backup_saves(game_dir: &Path, keep: usize) -> Result<Option<PathBuf>> {
+        let saves_dir = game_dir.join("saves");
+        let has_worlds =
+            std::fs::read_dir(&saves_dir).is_ok_and(|mut entries| entries.next().is_some());
+        if !has_worlds {
+            return Ok(None);
+        }
+
+        let backups_dir = game_dir.join("backups");
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let archive = backups_dir.join(format!("saves-{timestamp}.zip"));
+        launcher::archive::zip_directory(&saves_dir, &archive, &[])?;
+
+        // Timestamped names sort chronologically
+        let mut backups: Vec<PathBuf> = std::fs::read_dir(&backups_dir)
+            .context("Failed to read backups directory")?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("saves-") && name.ends_with(".zip"))
+            })
+            .collect();
+        backups.sort();
+        let excess = backups.len().saturating_sub(keep.max(1));
+        for old in &backups[..excess] {
+            if let Err(e) = std::fs::remove_file(old) {
+                warn!("⚠ Failed to remove old backup {}: {e}", old.display());
+            }
+        }
+
+        Ok(Some(archive))
+    }
+
+    /// Print the start of the newest crash report written since the game was started
+    fn report_latest_crash(game_dir: &Path, since: std::time::SystemTime) {
+        const CRASH_REPORT_LINES: usize = 40;
+
+        let Ok(entries) = std::fs::read_dir(game_dir.join("crash-reports")) else {
+            return;
+        };
+        let latest = entries
+            .flatten()
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                (modified >= since).then(|| (modified, entry.path()))
+            })
+            .filter(|(_, path)| path.extension().is_some_and(|ext| ext == "txt"))
+            .max_by_key(|(modified, _)| *modified);
+        let Some((_, path)) = latest else {
+            return;
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return;
+        };
+
+        error!("❌ Minecraft crashed. Crash report: {}", path.display());
+        for line in content.lines().take(CRASH_REPORT_LINES) {
+            error!("    {line}");
+        }
+        if content.lines().count() > CRASH_REPORT_LINES {
+            info!("💡 See the full report for more details");
+        }
+    }
+
+    /// The process exit code, using the shell's `128 + signal` convention when killed by a signal
+    fn exit_code(status: std::process::ExitStatus) -> i32 {
+        if let Some(code) = status.code() {
+            return code;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return 128 + signal;
+            }
+        }
+
+        1
+    }
+
+    /// Assemble the full game command without starting it
+    fn build_command(
+        version_info: &VersionInfo,
+        auth: &AuthResult,
+        minecraft_dir: &MinecraftDir,
+        java_manager: &JavaManager,
+        config: &LauncherConfig,
+        instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
+    ) -> Result<Command> {
         // Get the appropriate Java installation for this Minecraft version
-        let java_installation = java_manager.get_java_for_minecraft(&version_info.id)?;
+        let java_installation =
+            java_manager.get_java_for_minecraft(&version_info.id, settings.strict_java)?;
         info!(
             "Using Java {} at {}",
             java_installation.major_version,
@@ -47,59 +616,131 @@ impl GameLauncher {
             &mut cmd,
             version_info,
             minecraft_dir,
+            config,
             instance,
+            settings,
         );
 
         // Add classpath
-        launcher::game::GameLauncher::add_classpath(&mut cmd, version_info, minecraft_dir)?;
+        let classpath = launcher::game::GameLauncher::build_classpath(
+            &version_info.id,
+            &version_info.libraries,
+            minecraft_dir,
+        )?;
+        launcher::game::GameLauncher::add_classpath(
+            &mut cmd,
+            classpath,
+            &version_info.id,
+            minecraft_dir,
+            java_installation.major_version,
+        )?;
 
         // Add main class
         cmd.arg(&version_info.main_class);
 
         // Add game arguments (with instance-specific game directory)
+        if settings.world.is_some()
+            && !launcher::game::GameLauncher::supports_feature(
+                version_info,
+                "is_quick_play_singleplayer",
+            )
+        {
+            tracing::warn!(
+                "⚠ Minecraft {} does not support quick play; ignoring the world setting",
+                version_info.id
+            );
+        }
         launcher::game::GameLauncher::add_game_arguments(
             &mut cmd,
             version_info,
             auth,
             minecraft_dir,
             instance,
+            settings,
         )?;
 
+        // Run Java through the instance's wrapper command, if any
+        if let Some(wrapper) = instance.and_then(|inst| inst.settings.wrapper.as_deref()) {
+            cmd = launcher::game::GameLauncher::wrap_command(&cmd, wrapper)?;
+        }
+
         // Set working directory to the game directory
         cmd.current_dir(&game_dir);
 
+        // Apply instance environment variables
+        if let Some(inst) = instance {
+            cmd.envs(&inst.settings.env);
+        }
+
         // Configure stdio
         cmd.stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
             .stdin(Stdio::inherit());
 
-        info!("Starting Minecraft process...");
-        info!("Java command: {cmd:?}");
-        if let Some(_inst) = instance {
-            info!("Game directory: {}", game_dir.display());
-        }
+        Ok(cmd)
+    }
 
-        // Launch the game
-        let mut child = cmd.spawn().context("Failed to start Minecraft process")?;
+    /// The program and arguments of a command, with the access token redacted
+    fn command_arguments(cmd: &Command, auth: &AuthResult) -> Vec<String> {
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| {
+                let arg = arg.to_string_lossy();
+                if auth.access_token.is_empty() {
+                    arg.into_owned()
+                } else {
+                    arg.replace(&auth.access_token, "<access-token>")
+                }
+            })
+            .collect()
+    }
 
-        info!("Minecraft process started with PID: {}", child.id());
+    /// Render a command as a copy-pasteable shell line, with the access token redacted
+    fn format_command(cmd: &Command, auth: &AuthResult) -> String {
+        launcher::game::GameLauncher::command_arguments(cmd, auth)
+            .iter()
+            .map(|arg| launcher::game::GameLauncher::quote_argument(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 
-        // Wait for the process to complete
-        let status = child
-            .wait()
-            .context("Failed to wait for Minecraft process")?;
+    /// Write a command to a file as a JSON array of program and arguments
+    fn write_command_file(cmd: &Command, auth: &AuthResult, path: &Path) -> Result<()> {
+        let arguments = launcher::game::GameLauncher::command_arguments(cmd, auth);
+        let json = serde_json::to_string_pretty(&arguments)
+            .context("Failed to serialize launch command")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write launch command to {}", path.display()))
+    }
 
-        if status.success() {
-            info!("Minecraft exited successfully");
+    /// Quote an argument for display if it contains spaces or shell metacharacters
+    fn quote_argument(arg: &str) -> String {
+        let needs_quoting = arg.is_empty()
+            || arg
+                .chars()
+                .any(|c| c.is_whitespace() || "\"'`$&|;<>()*?!#~".contains(c));
+        if !needs_quoting {
+            return arg.to_string();
+        }
+
+        if cfg!(windows) {
+            format!("\"{}\"", arg.replace('"', "\\\""))
         } else {
-            return Err(GameError::launch_failed(format!(
-                "Minecraft exited with code: {:?}",
-                status.code()
-            ))
-            .into());
+            format!("'{}'", arg.replace('\'', "'\\''"))
         }
+    }
 
-        Ok(())
+    /// Build `<wrapper...> java <args>` from a fully assembled Java command
+    fn wrap_command(java_cmd: &Command, wrapper: &[String]) -> Result<Command> {
+        let Some((program, wrapper_args)) = wrapper.split_first() else {
+            return Err(GameError::launch_failed("Wrapper command is empty".to_string()).into());
+        };
+
+        let mut cmd = Command::new(program);
+        cmd.args(wrapper_args)
+            .arg(java_cmd.get_program())
+            .args(java_cmd.get_args());
+        Ok(cmd)
     }
 
     /// Add JVM arguments to the command
@@ -107,7 +748,9 @@ impl GameLauncher {
         cmd: &mut Command,
         version_info: &VersionInfo,
         minecraft_dir: &MinecraftDir,
+        config: &LauncherConfig,
         instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
     ) {
         // Use instance-specific memory settings or defaults
         let (min_mem, max_mem) = if let Some(inst) = instance {
@@ -134,45 +777,174 @@ impl GameLauncher {
             "-XX:G1HeapRegionSize=32M",
         ]);
 
-        // Add custom Java arguments from instance
-        if let Some(inst) = instance {
-            for arg in &inst.settings.java_args {
-                cmd.arg(arg);
-            }
-        }
+        // Add global Java arguments, overridden by the instance's own
+        let instance_args = instance.map_or(&[][..], |inst| &inst.settings.java_args);
+        cmd.args(launcher::game::GameLauncher::merge_jvm_args(
+            &config.jvm_args,
+            instance_args,
+        ));
 
         // Add Minecraft-specific system properties
         cmd.args([
-            "-Dminecraft.launcher.brand=Redstonium",
-            "-Dminecraft.launcher.version=1.0.0",
+            format!("-Dminecraft.launcher.brand={}", settings.brand),
+            format!(
+                "-Dminecraft.launcher.version={}",
+                launcher::LAUNCHER_VERSION
+            ),
         ]);
 
+        // Point log4j at the version's patched logging config
+        if let Some(config) = version_info
+            .logging
+            .as_ref()
+            .and_then(|logging| logging.client.as_ref())
+        {
+            let path = minecraft_dir.log_config_path(&config.file.id);
+            if path.exists() {
+                cmd.arg(config.argument.replace("${path}", &path.to_string_lossy()));
+            } else {
+                tracing::warn!(
+                    "⚠ Logging config {} not found, launching without it",
+                    config.file.id
+                );
+            }
+        }
+
         // Add version-specific JVM arguments if present
         if let Some(arguments) = &version_info.arguments {
             if let Some(jvm_args) = &arguments.jvm {
                 for arg in jvm_args {
+                    // The classpath is added separately, possibly through an argfile,
+                    // so the version's own `-cp ${classpath}` pair is left out
+                    if let ArgumentValue::Simple(value) = arg
+                        && (value == "-cp" || value == "${classpath}")
+                    {
+                        continue;
+                    }
                     launcher::game::GameLauncher::add_conditional_jvm_argument(
                         cmd,
                         arg,
                         version_info,
                         minecraft_dir,
                         instance,
+                        settings,
                     );
                 }
             }
         }
+
+        // Legacy versions never set this, and a malformed version JSON or merged loader
+        // profile may drop it; without it LWJGL can't find its natives
+        launcher::game::GameLauncher::ensure_library_path(
+            cmd,
+            &minecraft_dir.natives_dir(&version_info.id),
+        );
     }
 
-    /// Add classpath to the command
+    /// Point `java.library.path` at the natives directory unless an argument already set it
+    fn ensure_library_path(cmd: &mut Command, natives_dir: &Path) {
+        let already_set = cmd
+            .get_args()
+            .any(|arg| arg.to_string_lossy().starts_with("-Djava.library.path="));
+        if !already_set {
+            debug!("No java.library.path among the JVM arguments, using the natives directory");
+            cmd.arg(format!("-Djava.library.path={}", natives_dir.display()));
+        }
+    }
+
+    /// Merge global and instance JVM arguments, dropping global flags the instance overrides
+    fn merge_jvm_args(global: &[String], instance: &[String]) -> Vec<String> {
+        let overridden: HashSet<String> = instance
+            .iter()
+            .map(|arg| launcher::game::GameLauncher::jvm_flag_key(arg))
+            .collect();
+
+        global
+            .iter()
+            .filter(|arg| !overridden.contains(&launcher::game::GameLauncher::jvm_flag_key(arg)))
+            .chain(instance)
+            .cloned()
+            .collect()
+    }
+
+    /// The part of a JVM flag that identifies it, ignoring its value
+    ///
+    /// `-Xmx4G` -> `-Xmx`, `-Dfoo=bar` -> `-Dfoo`, `-XX:+UseG1GC` -> `-XX:UseG1GC`
+    fn jvm_flag_key(arg: &str) -> String {
+        const SIZED_FLAGS: [&str; 4] = ["-Xmx", "-Xms", "-Xss", "-Xmn"];
+
+        if let Some(flag) = SIZED_FLAGS.iter().find(|flag| arg.starts_with(**flag)) {
+            return (*flag).to_string();
+        }
+        if let Some(option) = arg.strip_prefix("-XX:") {
+            let name = option.trim_start_matches(['+', '-']);
+            let name = name.split_once('=').map_or(name, |(name, _)| name);
+            return format!("-XX:{name}");
+        }
+        arg.split_once('=').map_or(arg, |(key, _)| key).to_string()
+    }
+
+    /// Add the classpath, moving it into a Java `@argfile` when the command would be too long
     fn add_classpath(
         cmd: &mut Command,
-        version_info: &VersionInfo,
+        classpath: String,
+        version_id: &str,
         minecraft_dir: &MinecraftDir,
+        java_major: u32,
     ) -> Result<()> {
+        let command_length: usize = cmd.get_args().map(|arg| arg.len() + 1).sum::<usize>()
+            + cmd.get_program().len()
+            + classpath.len()
+            + GAME_ARGS_HEADROOM;
+
+        if command_length <= MAX_COMMAND_LINE {
+            cmd.arg("-cp").arg(classpath);
+            return Ok(());
+        }
+
+        // Argfiles were added in Java 9
+        if java_major < 9 {
+            tracing::warn!(
+                "⚠ Launch command is about {command_length} characters, which may exceed the system limit, but Java {java_major} does not support argfiles"
+            );
+            cmd.arg("-cp").arg(classpath);
+            return Ok(());
+        }
+
+        let argfile = minecraft_dir.classpath_argfile_path(version_id);
+        let content = format!(
+            "-cp {}\n",
+            launcher::game::GameLauncher::quote_argfile_value(&classpath)
+        );
+        std::fs::write(&argfile, content)
+            .with_context(|| format!("Failed to write classpath argfile: {}", argfile.display()))?;
+
+        info!(
+            "Classpath is too long for the command line, using argfile {}",
+            argfile.display()
+        );
+        cmd.arg(format!("@{}", argfile.display()));
+        Ok(())
+    }
+
+    /// Quote a value for a Java argfile, where backslash is the escape character
+    fn quote_argfile_value(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    /// Build the classpath string from the game JAR and library JARs
+    ///
+    /// Entries are joined with the platform separator and passed to Java as a single
+    /// argument, so paths containing spaces need no quoting.
+    fn build_classpath(
+        version_id: &str,
+        libraries: &[Library],
+        minecraft_dir: &MinecraftDir,
+    ) -> Result<String> {
         let mut classpath = Vec::new();
 
         // Add main game JAR
-        let game_jar = minecraft_dir.version_jar_path(&version_info.id);
+        let game_jar = minecraft_dir.version_jar_path(version_id);
         if game_jar.exists() {
             debug!(
                 "Adding to classpath (main game JAR): {}",
@@ -189,12 +961,7 @@ impl GameLauncher {
         }
 
         // Add library JARs
-        for library in &version_info.libraries {
-            if !library.should_use() {
-                debug!("Skipping library (rules): {}", library.name);
-                continue;
-            }
-
+        for library in launcher::game::GameLauncher::classpath_libraries(libraries) {
             // Check if this library is primarily a native library (e.g., name contains ":natives-")
             // Such libraries should not have their "artifact" (which is the native jar itself) added to the classpath.
             // Their contents are handled by java.library.path.
@@ -251,14 +1018,58 @@ impl GameLauncher {
             .into());
         }
 
-        // Join classpath with platform-specific separator
+        // Join classpath with platform-specific separator. An entry containing the
+        // separator would be split by Java, so refuse it rather than load the wrong files.
         let separator = if cfg!(windows) { ";" } else { ":" };
+        if let Some(entry) = classpath.iter().find(|entry| entry.contains(separator)) {
+            return Err(GameError::launch_failed(format!(
+                "Classpath entry contains the path separator '{separator}': {entry}"
+            ))
+            .into());
+        }
         let classpath_str = classpath.join(separator);
 
         debug!("Final Classpath: {classpath_str}");
-        cmd.args(["-cp", &classpath_str]);
+        Ok(classpath_str)
+    }
 
-        Ok(())
+    /// Libraries allowed on this platform, with duplicates collapsed to the highest version
+    ///
+    /// Each library keeps the position of its first occurrence so the order is deterministic.
+    fn classpath_libraries(libraries: &[Library]) -> Vec<&Library> {
+        let mut selected: Vec<&Library> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+
+        for library in libraries {
+            if !library.should_use() {
+                debug!("Skipping library (rules): {}", library.name);
+                continue;
+            }
+
+            let key = library.maven_key();
+            if let Some(&index) = positions.get(&key) {
+                let existing = selected[index];
+                if compare_library_versions(library.maven_version(), existing.maven_version())
+                    .is_gt()
+                {
+                    debug!(
+                        "Replacing duplicate library {} with {}",
+                        existing.name, library.name
+                    );
+                    selected[index] = library;
+                } else {
+                    debug!(
+                        "Skipping duplicate library {} (keeping {})",
+                        library.name, existing.name
+                    );
+                }
+            } else {
+                positions.insert(key, selected.len());
+                selected.push(library);
+            }
+        }
+
+        selected
     }
 
     /// Add game arguments to the command
@@ -268,6 +1079,7 @@ impl GameLauncher {
         auth: &AuthResult,
         minecraft_dir: &MinecraftDir,
         instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
     ) -> Result<()> {
         // Handle modern argument format (1.13+)
         if let Some(arguments) = &version_info.arguments {
@@ -280,6 +1092,7 @@ impl GameLauncher {
                         auth,
                         minecraft_dir,
                         instance,
+                        settings,
                     );
                 }
             }
@@ -292,9 +1105,15 @@ impl GameLauncher {
                 auth,
                 minecraft_dir,
                 instance,
+                settings,
             )?;
             cmd.args(args);
 
+            // Legacy versions have no feature rules, so demo mode is requested directly
+            if settings.demo {
+                cmd.arg("--demo");
+            }
+
             // For legacy versions, add essential arguments that might be missing
             launcher::game::GameLauncher::add_essential_arguments(
                 cmd,
@@ -304,6 +1123,16 @@ impl GameLauncher {
             );
         }
 
+        // Versions before quick play join a server through --server/--port
+        if let Some(server) = &settings.server {
+            if !launcher::game::GameLauncher::supports_feature(
+                version_info,
+                "is_quick_play_multiplayer",
+            ) {
+                cmd.args(["--server", &server.host, "--port", &server.port.to_string()]);
+            }
+        }
+
         Ok(())
     }
 
@@ -315,6 +1144,7 @@ impl GameLauncher {
         auth: &AuthResult,
         minecraft_dir: &MinecraftDir,
         instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
     ) {
         match arg {
             ArgumentValue::Simple(value) => {
@@ -324,16 +1154,18 @@ impl GameLauncher {
                     auth,
                     minecraft_dir,
                     instance,
+                    settings,
                 );
                 // Filter out problematic arguments
-                if launcher::game::GameLauncher::should_skip_argument(&resolved) {
+                if launcher::game::GameLauncher::should_skip_argument(&resolved, settings) {
                     return;
                 }
                 cmd.arg(resolved);
             }
+            // Conditional demo arguments are already gated by the `is_demo_user` feature
             ArgumentValue::Conditional { rules, value } => {
                 // Check if rules match current environment
-                if launcher::game::GameLauncher::evaluate_rules(rules) {
+                if launcher::game::GameLauncher::evaluate_rules(rules, settings) {
                     match value {
                         ArgumentValueType::Single(val) => {
                             let resolved = launcher::game::GameLauncher::resolve_argument_variables(
@@ -342,11 +1174,8 @@ impl GameLauncher {
                                 auth,
                                 minecraft_dir,
                                 instance,
+                                settings,
                             );
-                            // Filter out problematic arguments
-                            if launcher::game::GameLauncher::should_skip_argument(&resolved) {
-                                return;
-                            }
                             cmd.arg(resolved);
                         }
                         ArgumentValueType::Multiple(vals) => {
@@ -358,11 +1187,8 @@ impl GameLauncher {
                                         auth,
                                         minecraft_dir,
                                         instance,
+                                        settings,
                                     );
-                                // Filter out problematic arguments
-                                if launcher::game::GameLauncher::should_skip_argument(&resolved) {
-                                    continue;
-                                }
                                 cmd.arg(resolved);
                             }
                         }
@@ -379,6 +1205,7 @@ impl GameLauncher {
         version_info: &VersionInfo,
         minecraft_dir: &MinecraftDir,
         instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
     ) {
         match arg {
             ArgumentValue::Simple(value) => {
@@ -387,12 +1214,13 @@ impl GameLauncher {
                     version_info,
                     minecraft_dir,
                     instance,
+                    settings,
                 );
                 cmd.arg(resolved);
             }
             ArgumentValue::Conditional { rules, value } => {
                 // Check if rules match current environment
-                if launcher::game::GameLauncher::evaluate_rules(rules) {
+                if launcher::game::GameLauncher::evaluate_rules(rules, settings) {
                     match value {
                         ArgumentValueType::Single(val) => {
                             let resolved =
@@ -401,6 +1229,7 @@ impl GameLauncher {
                                     version_info,
                                     minecraft_dir,
                                     instance,
+                                    settings,
                                 );
                             cmd.arg(resolved);
                         }
@@ -412,6 +1241,7 @@ impl GameLauncher {
                                         version_info,
                                         minecraft_dir,
                                         instance,
+                                        settings,
                                     );
                                 cmd.arg(resolved);
                             }
@@ -428,6 +1258,7 @@ impl GameLauncher {
         auth: &AuthResult,
         minecraft_dir: &MinecraftDir,
         instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
     ) -> Result<Vec<String>> {
         let mut args = Vec::new();
 
@@ -447,7 +1278,7 @@ impl GameLauncher {
                 args.push(resolved);
             } else {
                 // Filter out problematic arguments
-                if launcher::game::GameLauncher::should_skip_argument(part) {
+                if launcher::game::GameLauncher::should_skip_argument(part, settings) {
                     continue;
                 }
                 args.push(part.to_string());
@@ -464,6 +1295,7 @@ impl GameLauncher {
         auth: &AuthResult,
         minecraft_dir: &MinecraftDir,
         instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
     ) -> std::string::String {
         let mut resolved = arg.to_string();
 
@@ -486,8 +1318,8 @@ impl GameLauncher {
             "${version_type}",
             &format!("{:?}", version_info.version_type).to_lowercase(),
         );
-        resolved = resolved.replace("${launcher_name}", "Redstonium");
-        resolved = resolved.replace("${launcher_version}", "1.0.0");
+        resolved = resolved.replace("${launcher_name}", &settings.brand);
+        resolved = resolved.replace("${launcher_version}", launcher::LAUNCHER_VERSION);
         resolved = resolved.replace(
             "${natives_directory}",
             &minecraft_dir
@@ -507,20 +1339,21 @@ impl GameLauncher {
         resolved = resolved.replace("${resolution_width}", "854"); // Default resolution
         resolved = resolved.replace("${resolution_height}", "480"); // Default resolution
 
-        // Handle Quick Play variables by not passing them if they're empty
-        // This prevents the "Only one quick play option can be specified" error
-        if resolved.contains("${quickPlayPath}")
-            || resolved.contains("${quickPlaySingleplayer}")
-            || resolved.contains("${quickPlayMultiplayer}")
-            || resolved.contains("${quickPlayRealms}")
-        {
-            // If the argument contains Quick Play variables, replace them with empty
-            // The should_skip_argument function will filter these out
-            resolved = resolved.replace("${quickPlayPath}", "");
-            resolved = resolved.replace("${quickPlaySingleplayer}", "");
-            resolved = resolved.replace("${quickPlayMultiplayer}", "");
-            resolved = resolved.replace("${quickPlayRealms}", "");
-        }
+        // Quick Play arguments are only emitted when their feature is enabled
+        resolved = resolved.replace(
+            "${quickPlaySingleplayer}",
+            settings.world.as_deref().unwrap_or_default(),
+        );
+        resolved = resolved.replace("${quickPlayPath}", "");
+        resolved = resolved.replace(
+            "${quickPlayMultiplayer}",
+            &settings
+                .server
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        );
+        resolved = resolved.replace("${quickPlayRealms}", "");
 
         resolved
     }
@@ -531,6 +1364,7 @@ impl GameLauncher {
         version_info: &VersionInfo,
         minecraft_dir: &MinecraftDir,
         instance: Option<&InstanceConfig>,
+        settings: &LaunchSettings,
     ) -> std::string::String {
         let mut resolved = arg.to_string();
 
@@ -553,8 +1387,8 @@ impl GameLauncher {
             "${version_type}",
             &format!("{:?}", version_info.version_type).to_lowercase(),
         );
-        resolved = resolved.replace("${launcher_name}", "Redstonium");
-        resolved = resolved.replace("${launcher_version}", "1.0.0");
+        resolved = resolved.replace("${launcher_name}", &settings.brand);
+        resolved = resolved.replace("${launcher_version}", launcher::LAUNCHER_VERSION);
         resolved = resolved.replace(
             "${natives_directory}",
             &minecraft_dir
@@ -622,60 +1456,363 @@ impl GameLauncher {
     }
 
     /// Evaluate rules for conditional arguments
-    fn evaluate_rules(rules: &[crate::launcher::version::Rule]) -> bool {
+    ///
+    /// As with library rules, the last rule that applies decides; if none does,
+    /// the argument is left out.
+    fn evaluate_rules(rules: &[crate::launcher::version::Rule], settings: &LaunchSettings) -> bool {
+        launcher::game::GameLauncher::evaluate_rules_for_os(rules, settings, env::consts::OS)
+    }
+
+    /// Evaluate rules as if running on `os`, a [`env::consts::OS`] name
+    fn evaluate_rules_for_os(
+        rules: &[crate::launcher::version::Rule],
+        settings: &LaunchSettings,
+        os: &str,
+    ) -> bool {
+        let mut allowed = false;
         for rule in rules {
-            let matches = if let Some(os_rule) = &rule.os {
-                if let Some(name) = &os_rule.name {
+            let os_matches = if let Some(os_rule) = &rule.os {
+                let name_matches = if let Some(name) = &os_rule.name {
                     match name.as_str() {
-                        "windows" => env::consts::OS == "windows",
-                        "linux" => env::consts::OS == "linux",
-                        "osx" => env::consts::OS == "macos",
+                        "windows" => os == "windows",
+                        "linux" => os == "linux",
+                        "osx" => os == "macos",
                         _ => false,
                     }
                 } else {
                     true
-                }
+                };
+                name_matches
+                    && os_rule.arch_matches()
+                    && os_rule.version_matches(crate::launcher::version::current_os_version())
             } else {
                 true
             };
 
-            if matches {
-                return rule.action == "allow";
+            let features_match = rule.features.as_ref().is_none_or(|features| {
+                features
+                    .iter()
+                    .all(|(feature, expected)| settings.feature_enabled(feature) == *expected)
+            });
+
+            if os_matches && features_match {
+                allowed = rule.action == "allow";
             }
         }
-        false
+        allowed
+    }
+
+    /// Whether the version has an argument gated on the given launcher feature
+    fn supports_feature(version_info: &VersionInfo, feature: &str) -> bool {
+        version_info
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.game.as_ref())
+            .is_some_and(|game_args| {
+                game_args.iter().any(|arg| match arg {
+                    ArgumentValue::Conditional { rules, .. } => rules.iter().any(|rule| {
+                        rule.features
+                            .as_ref()
+                            .is_some_and(|features| features.contains_key(feature))
+                    }),
+                    ArgumentValue::Simple(_) => false,
+                })
+            })
+    }
+
+    /// Check if an unconditional argument should be skipped to avoid conflicts
+    fn should_skip_argument(arg: &str, settings: &LaunchSettings) -> bool {
+        // Feature rules can't gate unconditional or legacy arguments, so a `--demo` there
+        // only applies when demo mode is on; owners never end up in demo mode by accident
+        arg == "--demo" && !settings.demo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher::version::LibraryDownloads;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
     }
 
-    /// Check if an argument should be skipped to avoid conflicts
-    fn should_skip_argument(arg: &str) -> bool {
-        // Skip demo argument when user has valid auth
-        if arg == "--demo" {
-            return true;
+    fn library(name: &str) -> Library {
+        Library {
+            name: name.to_string(),
+            downloads: LibraryDownloads {
+                artifact: None,
+                classifiers: None,
+            },
+            rules: None,
+            natives: None,
+            extract: None,
         }
+    }
+
+    #[test]
+    fn test_classpath_libraries_keeps_highest_duplicate() {
+        let libraries = vec![
+            library("org.ow2.asm:asm:9.3"),
+            library("com.google.guava:guava:31.1-jre"),
+            library("org.ow2.asm:asm:9.10"),
+            library("org.ow2.asm:asm:9.6"),
+            library("org.lwjgl:lwjgl:3.3.3:natives-linux"),
+            library("org.lwjgl:lwjgl:3.3.3"),
+        ];
+
+        let names: Vec<&str> = GameLauncher::classpath_libraries(&libraries)
+            .iter()
+            .map(|library| library.name.as_str())
+            .collect();
+
+        assert_eq!(
+            names,
+            [
+                "org.ow2.asm:asm:9.10",
+                "com.google.guava:guava:31.1-jre",
+                "org.lwjgl:lwjgl:3.3.3:natives-linux",
+                "org.lwjgl:lwjgl:3.3.3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classpath_with_spaces_in_base_path() {
+        let base_path = std::env::temp_dir()
+            .join(format!("redstonium test {}", uuid::Uuid::new_v4()))
+            .join("John Doe");
+        let minecraft_dir = MinecraftDir::at(&base_path).unwrap();
+
+        let mut lib = library("org.ow2.asm:asm:9.6");
+        lib.downloads.artifact = Some(crate::launcher::version::DownloadInfo {
+            sha1: String::new(),
+            size: 0,
+            url: String::new(),
+        });
+        let jar_path = minecraft_dir.version_jar_path("1.21");
+        let lib_path = minecraft_dir.library_path(&get_library_path(&lib.name));
+        for path in [&jar_path, &lib_path] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"").unwrap();
+        }
+
+        let classpath = GameLauncher::build_classpath("1.21", &[lib], &minecraft_dir).unwrap();
+        let separator = if cfg!(windows) { ";" } else { ":" };
+        assert_eq!(
+            classpath,
+            format!("{}{separator}{}", jar_path.display(), lib_path.display())
+        );
 
-        // Skip Quick Play arguments that are empty or contain empty values
-        if arg.starts_with("--quickPlayPath")
-            || arg.starts_with("--quickPlaySingleplayer")
-            || arg.starts_with("--quickPlayMultiplayer")
-            || arg.starts_with("--quickPlayRealms")
+        // The spawned process must receive the classpath as one intact argument
+        #[cfg(unix)]
         {
-            // Skip if the argument is just the flag with no value or empty value
-            if arg.contains('=') {
-                let parts: Vec<&str> = arg.split('=').collect();
-                if parts.len() > 1 && parts[1].is_empty() {
-                    return true;
-                }
-            }
-            // Also skip standalone Quick Play flags that would expect a following argument
-            if arg == "--quickPlayPath"
-                || arg == "--quickPlaySingleplayer"
-                || arg == "--quickPlayMultiplayer"
-                || arg == "--quickPlayRealms"
-            {
-                return true;
+            let output = Command::new("sh")
+                .args(["-c", "printf '%s' \"$1\"", "sh", &classpath])
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), classpath);
+        }
+
+        std::fs::remove_dir_all(base_path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_quote_argument() {
+        assert_eq!(GameLauncher::quote_argument("-Xmx2G"), "-Xmx2G");
+        if cfg!(windows) {
+            assert_eq!(
+                GameLauncher::quote_argument(r"C:\Users\John Doe"),
+                r#""C:\Users\John Doe""#
+            );
+        } else {
+            assert_eq!(
+                GameLauncher::quote_argument("/home/John Doe/.minecraft"),
+                "'/home/John Doe/.minecraft'"
+            );
+            assert_eq!(GameLauncher::quote_argument("it's"), r"'it'\''s'");
+        }
+    }
+
+    #[test]
+    fn test_quote_argfile_value() {
+        assert_eq!(
+            GameLauncher::quote_argfile_value(r"C:\Users\John Doe\lib.jar;C:\a.jar"),
+            r#""C:\\Users\\John Doe\\lib.jar;C:\\a.jar""#
+        );
+    }
+
+    #[test]
+    fn test_evaluate_rules_features() {
+        let rules = vec![crate::launcher::version::Rule {
+            action: "allow".to_string(),
+            os: None,
+            features: Some(HashMap::from([("is_demo_user".to_string(), true)])),
+        }];
+
+        assert!(!GameLauncher::evaluate_rules(
+            &rules,
+            &LaunchSettings::default()
+        ));
+        assert!(GameLauncher::evaluate_rules(
+            &rules,
+            &LaunchSettings {
+                demo: true,
+                ..LaunchSettings::default()
             }
+        ));
+    }
+
+    #[test]
+    fn test_launch_lock() {
+        let game_dir =
+            std::env::temp_dir().join(format!("redstonium-lock-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&game_dir).unwrap();
+
+        let lock = LaunchLock::acquire(&game_dir, false).unwrap().unwrap();
+        assert!(LaunchLock::acquire(&game_dir, false).is_err());
+        let forced = LaunchLock::acquire(&game_dir, true).unwrap().unwrap();
+        drop(forced);
+        drop(lock);
+        assert!(!game_dir.join(LAUNCH_LOCK_FILE).exists());
+
+        // A lock whose process is gone is replaced
+        std::fs::write(game_dir.join(LAUNCH_LOCK_FILE), u32::MAX.to_string()).unwrap();
+        let mut lock = LaunchLock::acquire(&game_dir, false).unwrap().unwrap();
+        lock.hand_over(u32::MAX - 1);
+        let content = std::fs::read_to_string(game_dir.join(LAUNCH_LOCK_FILE)).unwrap();
+        assert_eq!(content, (u32::MAX - 1).to_string());
+        drop(lock);
+        assert!(!game_dir.join(LAUNCH_LOCK_FILE).exists());
+
+        std::fs::remove_dir_all(&game_dir).unwrap();
+    }
+
+    #[test]
+    fn test_ensure_library_path() {
+        let natives_dir = Path::new("natives");
+
+        let mut cmd = Command::new("java");
+        GameLauncher::ensure_library_path(&mut cmd, natives_dir);
+        assert_eq!(
+            cmd.get_args().collect::<Vec<_>>(),
+            ["-Djava.library.path=natives"]
+        );
+
+        let mut cmd = Command::new("java");
+        cmd.arg("-Djava.library.path=/custom");
+        GameLauncher::ensure_library_path(&mut cmd, natives_dir);
+        assert_eq!(cmd.get_args().count(), 1);
+    }
+
+    #[test]
+    fn test_evaluate_rules_os() {
+        // As given for -XstartOnFirstThread in modern version JSON
+        let rules: Vec<crate::launcher::version::Rule> =
+            serde_json::from_str(r#"[{"action":"allow","os":{"name":"osx"}}]"#).unwrap();
+        let settings = LaunchSettings::default();
+        assert!(GameLauncher::evaluate_rules_for_os(
+            &rules, &settings, "macos"
+        ));
+        assert!(!GameLauncher::evaluate_rules_for_os(
+            &rules, &settings, "linux"
+        ));
+
+        // The last applicable rule wins
+        let rules: Vec<crate::launcher::version::Rule> = serde_json::from_str(
+            r#"[{"action":"allow"},{"action":"disallow","os":{"name":"osx"}}]"#,
+        )
+        .unwrap();
+        assert!(!GameLauncher::evaluate_rules_for_os(
+            &rules, &settings, "macos"
+        ));
+        assert!(GameLauncher::evaluate_rules_for_os(
+            &rules, &settings, "windows"
+        ));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_start_on_first_thread_on_macos() {
+        let version_info: VersionInfo = serde_json::from_value(serde_json::json!({
+            "id": "1.21", "type": "release", "time": "", "releaseTime": "",
+            "downloads": {"client": {"sha1": "", "size": 0, "url": ""}},
+            "libraries": [], "mainClass": "net.minecraft.client.main.Main",
+            "assetIndex": {"id": "17", "sha1": "", "size": 0, "totalSize": 0, "url": ""},
+            "assets": "17",
+            "arguments": {"jvm": [
+                {"rules": [{"action": "allow", "os": {"name": "osx"}}],
+                 "value": ["-XstartOnFirstThread"]}
+            ]}
+        }))
+        .unwrap();
+        let minecraft_dir = MinecraftDir::at(std::env::temp_dir()).unwrap();
+        let settings = LaunchSettings::default();
+
+        let mut cmd = Command::new("java");
+        for arg in version_info
+            .arguments
+            .as_ref()
+            .unwrap()
+            .jvm
+            .as_ref()
+            .unwrap()
+        {
+            GameLauncher::add_conditional_jvm_argument(
+                &mut cmd,
+                arg,
+                &version_info,
+                &minecraft_dir,
+                None,
+                &settings,
+            );
         }
+        assert!(cmd.get_args().any(|arg| arg == "-XstartOnFirstThread"));
+    }
+
+    #[test]
+    fn test_server_address_parse() {
+        let parse = |address| ServerAddress::parse(address).ok();
+        let server = |host: &str, port| {
+            Some(ServerAddress {
+                host: host.to_string(),
+                port,
+            })
+        };
 
-        false
+        assert_eq!(parse("mc.example.com"), server("mc.example.com", 25565));
+        assert_eq!(
+            parse("mc.example.com:25570"),
+            server("mc.example.com", 25570)
+        );
+        assert_eq!(parse("[::1]:25570"), server("::1", 25570));
+        assert_eq!(parse("::1"), server("::1", 25565));
+        assert_eq!(parse("mc.example.com:port"), None);
+        assert_eq!(parse(":25565"), None);
+        assert_eq!(
+            server("::1", 25570).map(|s| s.to_string()).as_deref(),
+            Some("[::1]:25570")
+        );
+    }
+
+    #[test]
+    fn test_merge_jvm_args_instance_overrides_global() {
+        let global = args(&[
+            "-Xmx4G",
+            "-Djava.net.preferIPv4Stack=true",
+            "-XX:+UseZGC",
+            "-Duser.language=en",
+        ]);
+        let instance = args(&["-Xmx8G", "-XX:-UseZGC", "-Duser.language=de"]);
+
+        assert_eq!(
+            GameLauncher::merge_jvm_args(&global, &instance),
+            args(&[
+                "-Djava.net.preferIPv4Stack=true",
+                "-Xmx8G",
+                "-XX:-UseZGC",
+                "-Duser.language=de",
+            ])
+        );
     }
 }
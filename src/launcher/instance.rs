@@ -6,8 +6,16 @@ use tokio::fs;
 use tracing::{debug, info, warn};
 
 use crate::launcher::minecraft_dir::MinecraftDir;
+use crate::launcher::version::VersionManifest;
 
 const MAX_INSTANCE_NAME_LEN: usize = 64;
+/// Device names Windows refuses as file or directory names, in any case
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+/// Regenerable instance data left out of backups
+const BACKUP_EXCLUDES: [&str; 3] = ["logs", "crash-reports", "backups"];
 
 /// Configuration for a Minecraft instance
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -41,6 +49,35 @@ pub struct InstanceSettings {
     pub debug: bool,
     /// Custom server to connect to on launch
     pub server: Option<ServerConfig>,
+    /// Environment variables set for the game process
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Wrapper command the game is launched through (e.g. `gamemoderun`)
+    #[serde(default)]
+    pub wrapper: Option<Vec<String>>,
+    /// Singleplayer world to boot into via quick play, unless overridden on launch
+    #[serde(default)]
+    pub quick_play_world: Option<String>,
+    /// Zip the `saves` directory before every launch
+    #[serde(default)]
+    pub backup_before_launch: bool,
+    /// How many pre-launch world backups to keep (5 when unset)
+    #[serde(default)]
+    pub backups_to_keep: Option<usize>,
+    /// Require the exact Java major version the game needs (e.g. for older Forge)
+    #[serde(default)]
+    pub strict_java: bool,
+    /// Always start the game in demo mode
+    #[serde(default)]
+    pub demo: bool,
+}
+
+impl InstanceSettings {
+    /// How many pre-launch world backups to keep
+    pub fn backup_limit(&self) -> usize {
+        const DEFAULT_BACKUPS_TO_KEEP: usize = 5;
+        self.backups_to_keep.unwrap_or(DEFAULT_BACKUPS_TO_KEEP)
+    }
 }
 
 /// Server configuration for quick connect
@@ -62,7 +99,7 @@ pub struct ModsConfig {
 }
 
 /// Supported mod loaders
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ModLoader {
     Vanilla,
@@ -111,6 +148,23 @@ impl InstanceManager {
         Ok(manager)
     }
 
+    /// List instance names from disk without parsing their configs
+    ///
+    /// Used by shell completion, which must not pay for a full launcher start.
+    pub fn instance_names(minecraft_dir: &MinecraftDir) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(minecraft_dir.instances_dir()) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter(|entry| entry.path().join("instance.json").is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Load all instances from disk
     async fn load_instances(&mut self) -> Result<()> {
         let mut entries = fs::read_dir(&self.instances_dir)
@@ -178,12 +232,15 @@ impl InstanceManager {
     }
 
     /// Create a new instance (with version validation)
+    ///
+    /// The manifest is supplied by the caller so that it can be fetched before
+    /// the instance manager lock is taken.
     pub async fn create_instance(
         &mut self,
         name: String,
         version: String,
         description: Option<String>,
-        file_manager: &crate::launcher::FileManager,
+        manifest: &VersionManifest,
     ) -> Result<()> {
         // Check if instance already exists
         if self.instances.contains_key(&name) {
@@ -193,6 +250,39 @@ impl InstanceManager {
             .into());
         }
 
+        Self::validate_instance_name(&name)?;
+        Self::validate_version(&version, manifest)?;
+
+        let config = InstanceConfig {
+            name: name.clone(),
+            version,
+            description,
+            created: chrono::Utc::now(),
+            last_used: None,
+            settings: InstanceSettings::default(),
+            mods: ModsConfig::default(),
+        };
+
+        // Save to disk
+        self.save_instance_config(&config).await?;
+
+        // Create the game directory structure
+        self.ensure_instance_directory(&name)
+            .context("Failed to create instance game directories")?;
+
+        // Add to memory
+        self.instances.insert(name.clone(), config);
+
+        info!("Created instance: {name} with game directories");
+        Ok(())
+    }
+
+    /// Validate an instance name without touching the instance map
+    pub fn validate_instance_name(name: &str) -> Result<()> {
+        if name.is_empty() {
+            return Err(InstanceError::invalid_config("Instance name cannot be empty").into());
+        }
+
         // Validate instance name (alphanumeric, hyphens, underscores only)
         if !name
             .chars()
@@ -215,41 +305,28 @@ impl InstanceManager {
             .into());
         }
 
-        // Validate version exists in manifest
-        let manifest = file_manager.get_version_manifest().await?;
-        let valid_version = manifest.versions.iter().any(|v| v.id == version);
-        if !valid_version {
+        // Rejected everywhere so instance folders stay portable
+        if WINDOWS_RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
             return Err(InstanceError::invalid_config(format!(
-                "Minecraft version '{version}' does not exist. Use 'Redstonium list' to see valid versions.",
-
+                "'{name}' is a reserved device name on Windows and cannot be used as an instance name"
             ))
             .into());
         }
 
-        let config = InstanceConfig {
-            name: name.clone(),
-            version,
-            description,
-            created: chrono::Utc::now(),
-            last_used: None,
-            settings: InstanceSettings::default(),
-            mods: ModsConfig::default(),
-        };
-
-        // Save to disk
-        self.save_instance_config(&config).await?;
-
-        // Create the game directory structure
-        self.ensure_instance_directory(&name)
-            .context("Failed to create instance game directories")?;
-
-        // Add to memory
-        self.instances.insert(name.clone(), config);
-
-        info!("Created instance: {name} with game directories");
         Ok(())
     }
 
+    /// Check that a Minecraft version exists in the manifest
+    pub fn validate_version(version: &str, manifest: &VersionManifest) -> Result<()> {
+        if manifest.versions.iter().any(|v| v.id == version) {
+            return Ok(());
+        }
+        Err(InstanceError::invalid_config(format!(
+            "Minecraft version '{version}' does not exist. Use 'Redstonium list' to see valid versions.",
+        ))
+        .into())
+    }
+
     /// Delete an instance
     pub async fn delete_instance(&mut self, name: &str) -> Result<()> {
         if !self.instances.contains_key(name) {
@@ -273,6 +350,66 @@ impl InstanceManager {
         Ok(())
     }
 
+    /// Zip an instance's config and game files, leaving out logs and crash reports
+    ///
+    /// Returns the number of files archived.
+    pub fn backup_instance(&self, name: &str, output: &Path) -> Result<usize> {
+        if !self.instances.contains_key(name) {
+            return Err(
+                InstanceError::not_found(format!("Instance '{name}' does not exist")).into(),
+            );
+        }
+
+        crate::launcher::archive::zip_directory(
+            &self.get_instance_dir(name),
+            output,
+            &BACKUP_EXCLUDES,
+        )
+    }
+
+    /// Unpack an instance backup as a new instance
+    ///
+    /// The instance keeps its original name unless `name` is given. Returns the
+    /// name it was restored as.
+    pub async fn restore_instance(
+        &mut self,
+        archive: &Path,
+        name: Option<String>,
+    ) -> Result<String> {
+        let content = crate::launcher::archive::read_archive_file(archive, "instance.json")?
+            .ok_or_else(|| {
+                InstanceError::invalid_config(format!(
+                    "{} is not an instance backup (no instance.json)",
+                    archive.display()
+                ))
+            })?;
+        let mut config: InstanceConfig =
+            serde_json::from_str(&content).context("Failed to parse instance config")?;
+
+        let name = name.unwrap_or_else(|| config.name.clone());
+        Self::validate_instance_name(&name)?;
+        let instance_dir = self.get_instance_dir(&name);
+        if self.instances.contains_key(&name) || instance_dir.exists() {
+            return Err(InstanceError::already_exists(format!(
+                "Instance '{name}' already exists. Use --name to restore it under another name."
+            ))
+            .into());
+        }
+
+        if let Err(e) = crate::launcher::archive::extract_archive(archive, &instance_dir) {
+            let _ = std::fs::remove_dir_all(&instance_dir);
+            return Err(e);
+        }
+
+        config.name.clone_from(&name);
+        self.save_instance_config(&config).await?;
+        self.ensure_instance_directory(&name)?;
+        self.instances.insert(name.clone(), config);
+
+        info!("Restored instance: {name}");
+        Ok(name)
+    }
+
     /// Get an instance configuration
     pub fn get_instance(&self, name: &str) -> Option<&InstanceConfig> {
         self.instances.get(name)
@@ -329,6 +466,71 @@ impl InstanceManager {
         Ok(())
     }
 
+    /// Set the mod loader an instance runs with
+    pub async fn set_instance_loader(
+        &mut self,
+        name: &str,
+        loader: ModLoader,
+        loader_version: Option<String>,
+    ) -> Result<()> {
+        let Some(config) = self.instances.get_mut(name) else {
+            return Err(
+                InstanceError::not_found(format!("Instance '{name}' does not exist")).into(),
+            );
+        };
+        config.mods.loader = loader;
+        config.mods.loader_version = loader_version;
+        let config_clone = config.clone();
+        self.save_instance_config(&config_clone).await
+    }
+
+    /// Set or remove environment variables for an instance; an empty value removes the variable
+    pub async fn set_instance_env(&mut self, name: &str, vars: &[(String, String)]) -> Result<()> {
+        let Some(config) = self.instances.get_mut(name) else {
+            return Err(
+                InstanceError::not_found(format!("Instance '{name}' does not exist")).into(),
+            );
+        };
+
+        for (key, value) in vars {
+            if value.is_empty() {
+                config.settings.env.remove(key);
+            } else {
+                config.settings.env.insert(key.clone(), value.clone());
+            }
+        }
+
+        let config_clone = config.clone();
+        self.save_instance_config(&config_clone).await
+    }
+
+    /// Set or clear the wrapper command an instance is launched through
+    pub async fn set_instance_wrapper(
+        &mut self,
+        name: &str,
+        wrapper: Option<Vec<String>>,
+    ) -> Result<()> {
+        if wrapper
+            .as_ref()
+            .is_some_and(|w| w.is_empty() || w[0].is_empty())
+        {
+            return Err(InstanceError::invalid_config(
+                "Wrapper command cannot be empty".to_string(),
+            )
+            .into());
+        }
+
+        let Some(config) = self.instances.get_mut(name) else {
+            return Err(
+                InstanceError::not_found(format!("Instance '{name}' does not exist")).into(),
+            );
+        };
+        config.settings.wrapper = wrapper;
+
+        let config_clone = config.clone();
+        self.save_instance_config(&config_clone).await
+    }
+
     /// Create instance game directory and ensure it's properly set up
     pub fn ensure_instance_directory(&self, name: &str) -> Result<PathBuf> {
         let instance_dir = self.minecraft_dir.base_path.join("instances").join(name);
@@ -367,6 +569,18 @@ impl InstanceManager {
     }
 }
 
+impl std::fmt::Display for ModLoader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Vanilla => "vanilla",
+            Self::Forge => "forge",
+            Self::Fabric => "fabric",
+            Self::Quilt => "quilt",
+        };
+        f.write_str(name)
+    }
+}
+
 impl Default for ModsConfig {
     fn default() -> Self {
         Self {
@@ -376,3 +590,19 @@ impl Default for ModsConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_instance_name() {
+        assert!(InstanceManager::validate_instance_name("vanilla-1_21").is_ok());
+        assert!(InstanceManager::validate_instance_name("").is_err());
+        assert!(InstanceManager::validate_instance_name("..").is_err());
+        assert!(InstanceManager::validate_instance_name("my pack").is_err());
+        assert!(InstanceManager::validate_instance_name("CON").is_err());
+        assert!(InstanceManager::validate_instance_name("lpt1").is_err());
+        assert!(InstanceManager::validate_instance_name("console").is_ok());
+    }
+}
@@ -1,16 +1,36 @@
 use crate::error::{JavaError, Result, ResultExt};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, SystemTime};
 use tracing::{debug, warn};
 
 use crate::launcher;
 
-#[derive(Debug, Clone)]
+/// How long a Java scan is trusted before rescanning
+const JAVA_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize)]
 pub struct JavaInstallation {
     pub path: PathBuf,
     pub major_version: u32,
+    /// Full version as reported by `java -version`, e.g. "17.0.9" or "1.8.0_333"
+    pub full_version: Option<String>,
+    /// Distribution name such as "Eclipse Temurin", when `java -version` reveals it
+    pub vendor: Option<String>,
+}
+
+impl JavaInstallation {
+    /// Full version and vendor for display, e.g. "17.0.9, Eclipse Temurin"
+    pub fn details(&self) -> Option<String> {
+        let parts: Vec<&str> = [self.full_version.as_deref(), self.vendor.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -22,20 +42,83 @@ pub struct JavaVersion {
 
 pub struct JavaManager {
     pub installations: HashMap<u32, JavaInstallation>,
+    /// Executables deregistered with `java remove`, skipped when scanning
+    removed: Vec<PathBuf>,
 }
 
 impl JavaManager {
     pub fn new() -> Self {
         Self {
             installations: HashMap::new(),
+            removed: Vec::new(),
         }
     }
 
-    /// Initialize the Java manager by scanning for Java installations
-    pub fn initialize(&mut self) {
+    /// Initialize the Java manager from the scan cache, scanning if it is stale
+    /// or `use_cache` is false
+    pub async fn initialize(&mut self, cache_path: &Path, use_cache: bool) {
+        let cache = JavaCache::load(cache_path);
+        self.removed = cache
+            .as_ref()
+            .map(|cache| cache.removed.clone())
+            .unwrap_or_default();
+
+        if let Some(installations) = cache
+            .filter(|_| use_cache)
+            .and_then(JavaCache::into_fresh_installations)
+        {
+            debug!("Using cached Java installations");
+            self.installations = installations;
+        } else {
+            self.scan_and_cache(cache_path).await;
+        }
+        self.report_installations();
+    }
+
+    /// Scan for Java installations, ignoring and then replacing the scan cache
+    ///
+    /// Installations deregistered with `java remove` are found again.
+    pub async fn rescan(&mut self, cache_path: &Path) {
+        self.removed.clear();
+        self.scan_and_cache(cache_path).await;
+    }
+
+    /// Deregister an installation by major version or path so it is no longer used
+    pub fn remove(&mut self, target: &str, cache_path: &Path) -> Result<JavaInstallation> {
+        let target_path = PathBuf::from(target);
+        let found = self.installations.iter().find_map(|(major, installation)| {
+            let matches = target.parse::<u32>().ok() == Some(*major)
+                || installation.path == target_path
+                || (target_path.is_absolute() && installation.path.starts_with(&target_path));
+            matches.then_some(*major)
+        });
+        let Some(installation) = found.and_then(|major| self.installations.remove(&major)) else {
+            return Err(JavaError::not_found(format!(
+                "No detected Java installation matches '{target}'"
+            ))
+            .into());
+        };
+
+        self.removed.push(installation.path.clone());
+        self.save_cache(cache_path)?;
+        Ok(installation)
+    }
+
+    async fn scan_and_cache(&mut self, cache_path: &Path) {
         debug!("Scanning for Java installations...");
-        self.scan_java_installations();
+        self.installations.clear();
+        self.scan_java_installations().await;
+
+        if let Err(e) = self.save_cache(cache_path) {
+            debug!("Failed to save Java cache: {e}");
+        }
+    }
 
+    fn save_cache(&self, cache_path: &Path) -> Result<()> {
+        JavaCache::from_installations(&self.installations, &self.removed).save(cache_path)
+    }
+
+    fn report_installations(&self) {
         if self.installations.is_empty() {
             warn!(
                 "No Java installations found! Please ensure Java is installed and available in PATH or JAVA_HOME"
@@ -75,7 +158,13 @@ impl JavaManager {
     }
 
     /// Get the best Java installation for a Minecraft version
-    pub fn get_java_for_minecraft(&self, minecraft_version: &str) -> Result<&JavaInstallation> {
+    ///
+    /// With `strict`, only the exact required major version is accepted.
+    pub fn get_java_for_minecraft(
+        &self,
+        minecraft_version: &str,
+        strict: bool,
+    ) -> Result<&JavaInstallation> {
         let required_version = Self::get_required_java_version(minecraft_version);
 
         // First, try to find the exact required version
@@ -84,6 +173,13 @@ impl JavaManager {
             return Ok(installation);
         }
 
+        if strict {
+            return Err(JavaError::unsupported_version(format!(
+                "Minecraft {minecraft_version} requires exactly Java {required_version} with strict Java enabled, but it is not installed. Install Java {required_version} (or set JAVA_HOME to it) and run 'Redstonium java rescan'."
+            ))
+            .into());
+        }
+
         // If exact version not found, try to find a compatible higher version
         let mut compatible_versions: Vec<_> = self
             .installations
@@ -118,121 +214,33 @@ impl JavaManager {
     }
 
     /// Scan for Java installations in common locations
-    fn scan_java_installations(&mut self) {
-        // Check JAVA_HOME first
-        if let Ok(java_home) = env::var("JAVA_HOME") {
-            let java_path = PathBuf::from(java_home).join("bin").join(if cfg!(windows) {
-                "java.exe"
-            } else {
-                "java"
-            });
-
-            if let Ok(installation) =
-                launcher::java::JavaManager::probe_java_installation(&java_path)
-            {
-                debug!("Found Java via JAVA_HOME: {}", installation.path.display());
-                self.installations
-                    .insert(installation.major_version, installation);
-            }
-        }
-
-        // Check system PATH
-        let java_executable = if cfg!(windows) { "java.exe" } else { "java" };
-        if let Ok(installation) =
-            launcher::java::JavaManager::probe_java_installation_by_name(java_executable)
+    ///
+    /// Candidates are probed concurrently; when several share a major version the
+    /// first found wins, in the order JAVA_HOME, PATH, then common directories.
+    async fn scan_java_installations(&mut self) {
+        let probes = java_candidates().into_iter().map(|candidate| {
+            tokio::task::spawn_blocking(move || (candidate.source(), candidate.probe()))
+        });
+
+        for (source, result) in futures_util::future::join_all(probes)
+            .await
+            .into_iter()
+            .flatten()
         {
+            let Ok(installation) = result else {
+                continue;
+            };
+            if self.removed.contains(&installation.path) {
+                debug!("Skipping removed Java at {}", installation.path.display());
+                continue;
+            }
             if let std::collections::hash_map::Entry::Vacant(e) =
                 self.installations.entry(installation.major_version)
             {
-                debug!("Found Java via PATH: {}", installation.path.display());
+                debug!("Found Java via {source}: {}", installation.path.display());
                 e.insert(installation);
             }
         }
-
-        // Check common installation directories
-        self.scan_common_java_directories();
-    }
-
-    /// Scan common Java installation directories
-    fn scan_common_java_directories(&mut self) {
-        let common_paths = if cfg!(windows) {
-            vec![
-                r"C:\Program Files\Java",
-                r"C:\Program Files (x86)\Java",
-                r"C:\Program Files\Eclipse Adoptium",
-                r"C:\Program Files\AdoptOpenJDK",
-            ]
-        } else if cfg!(target_os = "macos") {
-            vec![
-                "/Library/Java/JavaVirtualMachines",
-                "/System/Library/Java/JavaVirtualMachines",
-                "/usr/libexec/java_home",
-            ]
-        } else {
-            vec!["/usr/lib/jvm", "/usr/java", "/opt/java", "/opt/jdk"]
-        };
-
-        for base_path in common_paths {
-            if let Ok(entries) = std::fs::read_dir(base_path) {
-                for entry in entries.flatten() {
-                    if entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
-                        let java_path = entry.path().join("bin").join(if cfg!(windows) {
-                            "java.exe"
-                        } else {
-                            "java"
-                        });
-
-                        if let Ok(installation) =
-                            launcher::java::JavaManager::probe_java_installation(&java_path)
-                        {
-                            if let std::collections::hash_map::Entry::Vacant(e) =
-                                self.installations.entry(installation.major_version)
-                            {
-                                debug!(
-                                    "Found Java in common directory: {}",
-                                    installation.path.display()
-                                );
-                                e.insert(installation);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Special handling for macOS java_home
-        if cfg!(target_os = "macos") {
-            self.scan_macos_java_home();
-        }
-    }
-
-    /// Scan Java installations using macOS `java_home` utility
-    fn scan_macos_java_home(&mut self) {
-        let versions = ["8", "11", "16", "17", "21"];
-
-        for version in &versions {
-            if let Ok(output) = Command::new("/usr/libexec/java_home")
-                .arg("-v")
-                .arg(version)
-                .output()
-            {
-                if output.status.success() {
-                    let java_home = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                    let java_path = PathBuf::from(java_home).join("bin").join("java");
-
-                    if let Ok(installation) =
-                        launcher::java::JavaManager::probe_java_installation(&java_path)
-                    {
-                        if let std::collections::hash_map::Entry::Vacant(e) =
-                            self.installations.entry(installation.major_version)
-                        {
-                            debug!("Found Java via java_home: {}", installation.path.display());
-                            e.insert(installation);
-                        }
-                    }
-                }
-            }
-        }
     }
 
     /// Probe a Java installation by executable name
@@ -252,9 +260,11 @@ impl JavaManager {
                             PathBuf::from(executable)
                         };
 
-                    return Ok(JavaInstallation {
+                    return Self::check_target_arch(JavaInstallation {
                         path,
                         major_version: version.major,
+                        full_version: parse_java_full_version(&version_output),
+                        vendor: parse_java_vendor(&version_output),
                     });
                 }
             }
@@ -293,11 +303,241 @@ impl JavaManager {
             JavaError::version_parsing(format!("Failed to parse Java version: {version_output}"))
         })?;
 
-        Ok(JavaInstallation {
+        Self::check_target_arch(JavaInstallation {
             path: java_path.to_path_buf(),
             major_version: version.major,
+            full_version: parse_java_full_version(&version_output),
+            vendor: parse_java_vendor(&version_output),
         })
     }
+
+    /// Reject installations built for a different architecture than the overridden target
+    ///
+    /// Without an override every installation is accepted, as before.
+    fn check_target_arch(installation: JavaInstallation) -> Result<JavaInstallation> {
+        let Some(target) = launcher::version::target_arch_override() else {
+            return Ok(installation);
+        };
+
+        let output = Command::new(&installation.path)
+            .args(["-XshowSettings:properties", "-version"])
+            .output()
+            .with_context(|| format!("Failed to execute Java: {}", installation.path.display()))?;
+        let arch = parse_java_arch(&String::from_utf8_lossy(&output.stderr));
+
+        match arch {
+            Some(arch) if arch == target => Ok(installation),
+            Some(arch) => Err(JavaError::not_found(format!(
+                "Java at {} is {arch}, not {target}",
+                installation.path.display()
+            ))
+            .into()),
+            None => Err(JavaError::version_parsing(format!(
+                "Failed to determine the architecture of Java at {}",
+                installation.path.display()
+            ))
+            .into()),
+        }
+    }
+}
+
+/// Java installations found by the last scan, saved between runs
+#[derive(Debug, Serialize, Deserialize)]
+struct JavaCache {
+    scanned_at: SystemTime,
+    /// JAVA_HOME at scan time; a different one may point at a new installation
+    java_home: Option<String>,
+    /// Architecture override at scan time, which filters installations
+    target_arch: Option<String>,
+    installations: Vec<CachedJava>,
+    /// Executables deregistered with `java remove`
+    #[serde(default)]
+    removed: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedJava {
+    path: PathBuf,
+    major_version: u32,
+    full_version: Option<String>,
+    vendor: Option<String>,
+    modified: Option<SystemTime>,
+}
+
+impl JavaCache {
+    fn from_installations(
+        installations: &HashMap<u32, JavaInstallation>,
+        removed: &[PathBuf],
+    ) -> Self {
+        Self {
+            scanned_at: SystemTime::now(),
+            java_home: env::var("JAVA_HOME").ok(),
+            target_arch: launcher::version::target_arch_override().map(str::to_string),
+            installations: installations
+                .values()
+                .map(|installation| CachedJava {
+                    path: installation.path.clone(),
+                    major_version: installation.major_version,
+                    full_version: installation.full_version.clone(),
+                    vendor: installation.vendor.clone(),
+                    modified: executable_modified(&installation.path),
+                })
+                .collect(),
+            removed: removed.to_vec(),
+        }
+    }
+
+    fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Cached installations, unless the cache has expired or an executable changed
+    ///
+    /// An empty scan is never trusted so newly installed Java is picked up next run.
+    fn into_fresh_installations(self) -> Option<HashMap<u32, JavaInstallation>> {
+        let age = SystemTime::now().duration_since(self.scanned_at).ok()?;
+        if self.installations.is_empty()
+            || age > JAVA_CACHE_TTL
+            || self.java_home != env::var("JAVA_HOME").ok()
+            || self.target_arch.as_deref() != launcher::version::target_arch_override()
+        {
+            return None;
+        }
+
+        let mut installations = HashMap::new();
+        for cached in self.installations {
+            if cached.modified.is_none() || executable_modified(&cached.path) != cached.modified {
+                debug!(
+                    "Java at {} changed since the last scan",
+                    cached.path.display()
+                );
+                return None;
+            }
+            installations.insert(
+                cached.major_version,
+                JavaInstallation {
+                    path: cached.path,
+                    major_version: cached.major_version,
+                    full_version: cached.full_version,
+                    vendor: cached.vendor,
+                },
+            );
+        }
+        Some(installations)
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize Java cache")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write Java cache: {}", path.display()))
+    }
+}
+
+fn executable_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// A place a Java installation may be found
+enum JavaCandidate {
+    /// A `java` executable path, with where it came from
+    Executable(PathBuf, &'static str),
+    /// The `java` found on PATH
+    OnPath,
+    /// The installation macOS `java_home` reports for a major version
+    MacJavaHome(&'static str),
+}
+
+impl JavaCandidate {
+    fn source(&self) -> &'static str {
+        match self {
+            Self::Executable(_, source) => source,
+            Self::OnPath => "PATH",
+            Self::MacJavaHome(_) => "java_home",
+        }
+    }
+
+    /// Run the candidate's `java -version`; blocks on the process
+    fn probe(self) -> Result<JavaInstallation> {
+        match self {
+            Self::Executable(path, _) => JavaManager::probe_java_installation(&path),
+            Self::OnPath => JavaManager::probe_java_installation_by_name(java_executable_name()),
+            Self::MacJavaHome(version) => {
+                let output = Command::new("/usr/libexec/java_home")
+                    .args(["-v", version])
+                    .output()
+                    .context("Failed to run java_home")?;
+                if !output.status.success() {
+                    return Err(
+                        JavaError::not_found(format!("java_home found no Java {version}")).into(),
+                    );
+                }
+                let java_home = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                JavaManager::probe_java_installation(
+                    &PathBuf::from(java_home).join("bin").join("java"),
+                )
+            }
+        }
+    }
+}
+
+fn java_executable_name() -> &'static str {
+    if cfg!(windows) { "java.exe" } else { "java" }
+}
+
+/// Every Java candidate to probe, in priority order
+fn java_candidates() -> Vec<JavaCandidate> {
+    let mut candidates = Vec::new();
+
+    if let Ok(java_home) = env::var("JAVA_HOME") {
+        candidates.push(JavaCandidate::Executable(
+            PathBuf::from(java_home)
+                .join("bin")
+                .join(java_executable_name()),
+            "JAVA_HOME",
+        ));
+    }
+
+    candidates.push(JavaCandidate::OnPath);
+
+    let common_paths = if cfg!(windows) {
+        vec![
+            r"C:\Program Files\Java",
+            r"C:\Program Files (x86)\Java",
+            r"C:\Program Files\Eclipse Adoptium",
+            r"C:\Program Files\AdoptOpenJDK",
+        ]
+    } else if cfg!(target_os = "macos") {
+        vec![
+            "/Library/Java/JavaVirtualMachines",
+            "/System/Library/Java/JavaVirtualMachines",
+        ]
+    } else {
+        vec!["/usr/lib/jvm", "/usr/java", "/opt/java", "/opt/jdk"]
+    };
+
+    for base_path in common_paths {
+        let Ok(entries) = std::fs::read_dir(base_path) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+                let java_path = entry.path().join("bin").join(java_executable_name());
+                if java_path.exists() {
+                    candidates.push(JavaCandidate::Executable(java_path, "common directory"));
+                }
+            }
+        }
+    }
+
+    if cfg!(target_os = "macos") {
+        for version in ["8", "11", "16", "17", "21"] {
+            candidates.push(JavaCandidate::MacJavaHome(version));
+        }
+    }
+
+    candidates
 }
 
 /// Parse Java version from version output
@@ -359,6 +599,59 @@ fn parse_java_version(version_output: &str) -> Option<JavaVersion> {
     None
 }
 
+/// The quoted version on the first `version` line of `java -version` output
+fn parse_java_full_version(version_output: &str) -> Option<String> {
+    let line = version_output
+        .lines()
+        .find(|line| line.contains("version"))?;
+    let (_, rest) = line.split_once('"')?;
+    let (version, _) = rest.split_once('"')?;
+    Some(version.to_string())
+}
+
+/// Identify the Java distribution from `java -version` output
+///
+/// Most builds name themselves in the runtime line, e.g.
+/// `OpenJDK Runtime Environment Temurin-17.0.9+9 (build 17.0.9+9)`.
+fn parse_java_vendor(version_output: &str) -> Option<String> {
+    const VENDORS: [(&str, &str); 10] = [
+        ("Temurin", "Eclipse Temurin"),
+        ("Zulu", "Azul Zulu"),
+        ("GraalVM", "GraalVM"),
+        ("Corretto", "Amazon Corretto"),
+        ("Microsoft", "Microsoft"),
+        ("Semeru", "IBM Semeru"),
+        ("Red_Hat", "Red Hat"),
+        ("BellSoft", "BellSoft Liberica"),
+        ("JBR", "JetBrains Runtime"),
+        ("Homebrew", "Homebrew"),
+    ];
+
+    if let Some((_, vendor)) = VENDORS
+        .iter()
+        .find(|(marker, _)| version_output.contains(marker))
+    {
+        return Some((*vendor).to_string());
+    }
+    if version_output.contains("Java(TM)") {
+        return Some("Oracle".to_string());
+    }
+    version_output
+        .trim_start()
+        .starts_with("openjdk")
+        .then(|| "OpenJDK".to_string())
+}
+
+/// Parse the `os.arch` property from `-XshowSettings:properties` output
+fn parse_java_arch(settings_output: &str) -> Option<&'static str> {
+    settings_output.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "os.arch")
+            .then(|| launcher::version::normalize_arch(value))
+            .flatten()
+    })
+}
+
 #[allow(clippy::match_same_arms)]
 /// Parse Minecraft version to extract major and minor version numbers
 fn parse_minecraft_version(version: &str) -> Option<(u32, u32)> {
@@ -434,6 +727,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_java_vendor() {
+        let temurin = "openjdk version \"17.0.9\" 2023-10-17\n\
+            OpenJDK Runtime Environment Temurin-17.0.9+9 (build 17.0.9+9)\n";
+        assert_eq!(parse_java_full_version(temurin).as_deref(), Some("17.0.9"));
+        assert_eq!(
+            parse_java_vendor(temurin).as_deref(),
+            Some("Eclipse Temurin")
+        );
+
+        let oracle =
+            "java version \"1.8.0_333\"\nJava(TM) SE Runtime Environment (build 1.8.0_333-b02)\n";
+        assert_eq!(parse_java_vendor(oracle).as_deref(), Some("Oracle"));
+
+        let plain = "openjdk version \"21.0.1\" 2023-10-17\nOpenJDK Runtime Environment (build 21.0.1+12)\n";
+        assert_eq!(parse_java_vendor(plain).as_deref(), Some("OpenJDK"));
+        assert_eq!(parse_java_vendor("unexpected"), None);
+    }
+
+    #[test]
+    fn test_parse_java_arch() {
+        let output = "Property settings:\n    java.version = 17.0.4\n    os.arch = amd64\n";
+        assert_eq!(parse_java_arch(output), Some("x86_64"));
+        assert_eq!(parse_java_arch("    os.arch = aarch64"), Some("aarch64"));
+        assert_eq!(parse_java_arch("openjdk version \"17.0.4\""), None);
+    }
+
     #[test]
     fn test_parse_minecraft_version() {
         assert_eq!(parse_minecraft_version("1.20.4"), Some((1, 20)));
@@ -0,0 +1,449 @@
+use crate::error::{GameError, InstanceError, Result, ResultExt};
+use crate::launcher::files::{FileManager, get_library_path};
+use crate::launcher::instance::{ModLoader, ModsConfig};
+use crate::launcher::minecraft_dir::MinecraftDir;
+use crate::launcher::version::{Arguments, DownloadInfo, Library, LibraryDownloads, VersionInfo};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::{HashMap, HashSet};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
+const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
+const FORGE_FILES_URL: &str = "https://files.minecraftforge.net/net/minecraftforge/forge";
+
+/// A loader release offered by a loader's meta API
+#[derive(Debug, Clone)]
+pub struct LoaderVersion {
+    pub version: String,
+    /// Whether this is a stable release rather than a beta
+    pub stable: bool,
+    /// Whether the loader's maintainers recommend this release
+    pub recommended: bool,
+}
+
+/// A loader's launcher profile, layered on top of the vanilla version it inherits from
+///
+/// Stored as `versions/<id>/<id>.json` with every library resolved to a verifiable download.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoaderProfile {
+    pub id: String,
+    #[serde(rename = "inheritsFrom")]
+    pub inherits_from: String,
+    #[serde(rename = "mainClass")]
+    pub main_class: String,
+    #[serde(default)]
+    pub arguments: Option<Arguments>,
+    pub libraries: Vec<Library>,
+}
+
+/// Profile as served by a loader meta API; libraries are plain Maven coordinates
+#[derive(Debug, Deserialize)]
+struct MetaProfile {
+    #[serde(rename = "mainClass")]
+    main_class: String,
+    #[serde(default)]
+    arguments: Option<Arguments>,
+    libraries: Vec<MavenLibrary>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MavenLibrary {
+    name: String,
+    /// Base URL of the Maven repository hosting the library
+    url: String,
+}
+
+/// Entry in a Fabric or Quilt loader listing
+///
+/// Listings for a game version nest the loader next to its intermediary mappings.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum MetaLoaderEntry {
+    ForGame { loader: MetaLoader },
+    Loader(MetaLoader),
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaLoader {
+    version: String,
+    /// Only Fabric reports this; Quilt betas are recognised by their suffix
+    stable: Option<bool>,
+}
+
+/// Forge's `promotions_slim.json`: `<game version>-latest` and `-recommended` builds
+#[derive(Debug, Deserialize)]
+struct ForgePromotions {
+    promos: HashMap<String, String>,
+}
+
+/// Version ID of a loader profile, matching the loaders' own naming
+pub fn profile_id(loader: &ModLoader, loader_version: &str, minecraft_version: &str) -> String {
+    format!("{loader}-loader-{loader_version}-{minecraft_version}")
+}
+
+/// Releases of a loader, newest first, optionally only those for one Minecraft version
+///
+/// Forge builds are tied to a Minecraft version, so Forge requires one.
+pub async fn loader_versions(
+    file_manager: &FileManager,
+    loader: &ModLoader,
+    minecraft_version: Option<&str>,
+) -> Result<Vec<LoaderVersion>> {
+    match loader {
+        ModLoader::Quilt => {
+            meta_loader_versions(file_manager, QUILT_META_URL, minecraft_version).await
+        }
+        ModLoader::Fabric => {
+            meta_loader_versions(file_manager, FABRIC_META_URL, minecraft_version).await
+        }
+        ModLoader::Forge => {
+            let Some(minecraft_version) = minecraft_version else {
+                return Err(InstanceError::invalid_config(
+                    "Forge versions are listed per Minecraft version",
+                )
+                .into());
+            };
+            forge_loader_versions(file_manager, minecraft_version).await
+        }
+        ModLoader::Vanilla => Ok(Vec::new()),
+    }
+}
+
+/// Fabric and Quilt share the same meta API shape
+async fn meta_loader_versions(
+    file_manager: &FileManager,
+    meta_url: &str,
+    minecraft_version: Option<&str>,
+) -> Result<Vec<LoaderVersion>> {
+    let url = match minecraft_version {
+        Some(minecraft_version) => format!("{meta_url}/versions/loader/{minecraft_version}"),
+        None => format!("{meta_url}/versions/loader"),
+    };
+    let entries: Vec<MetaLoaderEntry> = file_manager
+        .fetch_json(&url)
+        .await
+        .context("Failed to list loader versions")?;
+
+    let mut versions: Vec<LoaderVersion> = entries
+        .into_iter()
+        .map(|entry| {
+            let (MetaLoaderEntry::ForGame { loader } | MetaLoaderEntry::Loader(loader)) = entry;
+            LoaderVersion {
+                stable: loader
+                    .stable
+                    .unwrap_or_else(|| !loader.version.contains('-')),
+                version: loader.version,
+                recommended: false,
+            }
+        })
+        .collect();
+    if let Some(newest_stable) = versions.iter_mut().find(|version| version.stable) {
+        newest_stable.recommended = true;
+    }
+    Ok(versions)
+}
+
+/// Forge builds for a Minecraft version, with the promoted recommended build marked
+async fn forge_loader_versions(
+    file_manager: &FileManager,
+    minecraft_version: &str,
+) -> Result<Vec<LoaderVersion>> {
+    let mut builds: HashMap<String, Vec<String>> = file_manager
+        .fetch_json(&format!("{FORGE_FILES_URL}/maven-metadata.json"))
+        .await
+        .context("Failed to list Forge versions")?;
+    let promotions: Option<ForgePromotions> = file_manager
+        .fetch_json(&format!("{FORGE_FILES_URL}/promotions_slim.json"))
+        .await
+        .ok();
+    let recommended = promotions.and_then(|promotions| {
+        promotions
+            .promos
+            .get(&format!("{minecraft_version}-recommended"))
+            .cloned()
+    });
+
+    // Builds are listed oldest first as `<game version>-<forge version>`
+    let prefix = format!("{minecraft_version}-");
+    Ok(builds
+        .remove(minecraft_version)
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .map(|build| {
+            let version = build.strip_prefix(&prefix).unwrap_or(&build).to_string();
+            LoaderVersion {
+                recommended: recommended.as_deref() == Some(version.as_str()),
+                stable: true,
+                version,
+            }
+        })
+        .collect())
+}
+
+/// Install Quilt for a Minecraft version, returning the installed loader version
+///
+/// The newest stable loader is used unless `loader_version` pins one. Loader libraries
+/// are downloaded now so the saved profile can record their checksums.
+pub async fn install_quilt(
+    file_manager: &FileManager,
+    minecraft_dir: &MinecraftDir,
+    minecraft_version: &str,
+    loader_version: Option<&str>,
+) -> Result<String> {
+    let versions =
+        loader_versions(file_manager, &ModLoader::Quilt, Some(minecraft_version)).await?;
+    let chosen = match loader_version {
+        Some(requested) => versions.iter().find(|v| v.version == requested),
+        None => versions
+            .iter()
+            .find(|v| v.stable)
+            .or_else(|| versions.first()),
+    };
+    let Some(chosen) = chosen else {
+        return Err(InstanceError::invalid_config(match loader_version {
+            Some(requested) => format!(
+                "Quilt loader {requested} is not available for Minecraft {minecraft_version}"
+            ),
+            None => format!("Quilt does not support Minecraft {minecraft_version}"),
+        })
+        .into());
+    };
+    let loader_version = chosen.version.clone();
+
+    info!("Installing Quilt loader {loader_version} for Minecraft {minecraft_version}...");
+    let meta: MetaProfile = file_manager
+        .fetch_json(&format!(
+            "{QUILT_META_URL}/versions/loader/{minecraft_version}/{loader_version}/profile/json"
+        ))
+        .await
+        .context("Failed to fetch the Quilt profile")?;
+
+    let mut libraries = Vec::with_capacity(meta.libraries.len());
+    for library in &meta.libraries {
+        libraries.push(resolve_maven_library(file_manager, minecraft_dir, library).await?);
+    }
+
+    let profile = LoaderProfile {
+        id: profile_id(&ModLoader::Quilt, &loader_version, minecraft_version),
+        inherits_from: minecraft_version.to_string(),
+        main_class: meta.main_class,
+        arguments: meta.arguments,
+        libraries,
+    };
+    save_profile(minecraft_dir, &profile).await?;
+
+    info!("✓ Installed Quilt loader {loader_version}");
+    Ok(loader_version)
+}
+
+/// Delete an installed loader profile, returning whether one was found
+pub fn remove_profile(
+    minecraft_dir: &MinecraftDir,
+    loader: &ModLoader,
+    loader_version: &str,
+    minecraft_version: &str,
+) -> Result<bool> {
+    let dir = minecraft_dir.version_dir(&profile_id(loader, loader_version, minecraft_version));
+    if !dir.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(&dir)
+        .with_context(|| format!("Failed to remove loader profile: {}", dir.display()))?;
+    Ok(true)
+}
+
+/// Layer an instance's loader profile over the vanilla version it launches
+///
+/// Vanilla instances are returned unchanged.
+pub fn apply_loader(
+    version_info: VersionInfo,
+    mods: &ModsConfig,
+    minecraft_dir: &MinecraftDir,
+) -> Result<VersionInfo> {
+    match &mods.loader {
+        ModLoader::Vanilla => return Ok(version_info),
+        ModLoader::Quilt => {}
+        loader => {
+            warn!("⚠ The {loader} loader is not supported yet; launching vanilla Minecraft");
+            return Ok(version_info);
+        }
+    }
+
+    let path = mods.loader_version.as_deref().map(|loader_version| {
+        minecraft_dir.version_json_path(&profile_id(&mods.loader, loader_version, &version_info.id))
+    });
+    let Some(content) = path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+    else {
+        return Err(GameError::preparation_failed(format!(
+            "{} is not installed for Minecraft {}. Run 'Redstonium instance loader {} <instance>' to install it.",
+            mods.loader, version_info.id, mods.loader
+        ))
+        .into());
+    };
+
+    let profile: LoaderProfile =
+        serde_json::from_str(&content).context("Failed to parse loader profile")?;
+    debug!("Launching with loader profile {}", profile.id);
+    Ok(merge_profile(version_info, profile))
+}
+
+/// Download a Maven library and describe it as a checksummed library download
+async fn resolve_maven_library(
+    file_manager: &FileManager,
+    minecraft_dir: &MinecraftDir,
+    library: &MavenLibrary,
+) -> Result<Library> {
+    let relative_path = get_library_path(&library.name);
+    let url = format!("{}/{relative_path}", library.url.trim_end_matches('/'));
+    let path = minecraft_dir.library_path(&relative_path);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create library directory: {}", parent.display()))?;
+    }
+    debug!("Downloading loader library: {}", library.name);
+    file_manager.download_to(&url, &path, None).await?;
+
+    let bytes = fs::read(&path)
+        .await
+        .with_context(|| format!("Failed to read library: {}", path.display()))?;
+    Ok(Library {
+        name: library.name.clone(),
+        downloads: LibraryDownloads {
+            artifact: Some(DownloadInfo {
+                sha1: format!("{:x}", Sha1::digest(&bytes)),
+                size: bytes.len() as u64,
+                url,
+            }),
+            classifiers: None,
+        },
+        rules: None,
+        natives: None,
+        extract: None,
+    })
+}
+
+async fn save_profile(minecraft_dir: &MinecraftDir, profile: &LoaderProfile) -> Result<()> {
+    let version_dir = minecraft_dir.version_dir(&profile.id);
+    fs::create_dir_all(&version_dir)
+        .await
+        .with_context(|| format!("Failed to create directory: {}", version_dir.display()))?;
+    let content =
+        serde_json::to_string_pretty(profile).context("Failed to serialize loader profile")?;
+    fs::write(minecraft_dir.version_json_path(&profile.id), content)
+        .await
+        .context("Failed to save loader profile")
+}
+
+/// Merge a loader profile into its vanilla version
+///
+/// The loader's main class wins, its arguments are appended, and its libraries come
+/// first, replacing vanilla libraries with the same group and artifact.
+fn merge_profile(mut base: VersionInfo, profile: LoaderProfile) -> VersionInfo {
+    let artifact_key = |name: &str| name.split(':').take(2).collect::<Vec<_>>().join(":");
+    let overridden: HashSet<String> = profile
+        .libraries
+        .iter()
+        .map(|library| artifact_key(&library.name))
+        .collect();
+    base.libraries
+        .retain(|library| !overridden.contains(&artifact_key(&library.name)));
+
+    let mut libraries = profile.libraries;
+    libraries.append(&mut base.libraries);
+    base.libraries = libraries;
+    base.main_class = profile.main_class;
+
+    if let Some(extra) = profile.arguments {
+        let arguments = base.arguments.get_or_insert(Arguments {
+            game: None,
+            jvm: None,
+        });
+        for (target, values) in [
+            (&mut arguments.game, extra.game),
+            (&mut arguments.jvm, extra.jvm),
+        ] {
+            if let Some(values) = values {
+                target.get_or_insert_with(Vec::new).extend(values);
+            }
+        }
+    }
+
+    base
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library(name: &str) -> Library {
+        serde_json::from_value(serde_json::json!({"name": name, "downloads": {}})).unwrap()
+    }
+
+    #[test]
+    fn test_merge_profile() {
+        let base: VersionInfo = serde_json::from_value(serde_json::json!({
+            "id": "1.20.1",
+            "type": "release",
+            "time": "2023-06-12T13:25:51+00:00",
+            "releaseTime": "2023-06-12T13:25:51+00:00",
+            "downloads": {"client": {"sha1": "00", "size": 0, "url": ""}},
+            "libraries": [
+                {"name": "org.ow2.asm:asm:9.3", "downloads": {}},
+                {"name": "com.mojang:brigadier:1.1.8", "downloads": {}}
+            ],
+            "mainClass": "net.minecraft.client.main.Main",
+            "arguments": {"game": ["--username"], "jvm": []},
+            "assetIndex": {"id": "5", "sha1": "00", "size": 0, "totalSize": 0, "url": ""},
+            "assets": "5"
+        }))
+        .unwrap();
+        let profile = LoaderProfile {
+            id: "quilt-loader-0.21.0-1.20.1".to_string(),
+            inherits_from: "1.20.1".to_string(),
+            main_class: "org.quiltmc.loader.impl.launch.knot.KnotClient".to_string(),
+            arguments: None,
+            libraries: vec![
+                library("org.quiltmc:quilt-loader:0.21.0"),
+                library("org.ow2.asm:asm:9.6"),
+            ],
+        };
+
+        let merged = merge_profile(base, profile);
+        assert_eq!(
+            merged.main_class,
+            "org.quiltmc.loader.impl.launch.knot.KnotClient"
+        );
+        let names: Vec<_> = merged.libraries.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "org.quiltmc:quilt-loader:0.21.0",
+                "org.ow2.asm:asm:9.6",
+                "com.mojang:brigadier:1.1.8"
+            ]
+        );
+        assert_eq!(merged.id, "1.20.1");
+    }
+
+    #[test]
+    fn test_parse_meta_loader_listing() {
+        let entries: Vec<MetaLoaderEntry> = serde_json::from_str(
+            r#"[{"loader": {"version": "0.26.0-beta.1"}, "intermediary": {}},
+                {"version": "0.15.3", "stable": true}]"#,
+        )
+        .unwrap();
+        assert!(
+            matches!(&entries[0], MetaLoaderEntry::ForGame { loader } if loader.stable.is_none())
+        );
+        assert!(
+            matches!(&entries[1], MetaLoaderEntry::Loader(loader) if loader.stable == Some(true))
+        );
+    }
+}
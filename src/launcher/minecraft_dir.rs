@@ -1,10 +1,17 @@
 use crate::error::{Result, ResultExt, RustifiedError};
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// File the official Minecraft launcher keeps in its `.minecraft`
+const OFFICIAL_LAUNCHER_MARKER: &str = "launcher_profiles.json";
 
 #[derive(Clone)]
 pub struct MinecraftDir {
     pub base_path: PathBuf,
+    /// `libraries/` to use instead of the one under `base_path`
+    libraries_override: Option<PathBuf>,
+    /// `assets/` to use instead of the one under `base_path`
+    assets_override: Option<PathBuf>,
 }
 
 impl MinecraftDir {
@@ -12,7 +19,46 @@ impl MinecraftDir {
         let base_path = Self::get_minecraft_dir()?;
         std::fs::create_dir_all(&base_path)?;
 
-        Ok(Self { base_path })
+        Ok(Self::with_base(base_path))
+    }
+
+    fn with_base(base_path: PathBuf) -> Self {
+        Self {
+            base_path,
+            libraries_override: None,
+            assets_override: None,
+        }
+    }
+
+    /// A directory with the standard `.minecraft` layout rooted at `base_path`, created if missing
+    pub fn at(base_path: impl Into<PathBuf>) -> Result<Self> {
+        let base_path = base_path.into();
+        std::fs::create_dir_all(&base_path)
+            .with_context(|| format!("Failed to create {}", base_path.display()))?;
+
+        Ok(Self::with_base(base_path))
+    }
+
+    /// The official launcher's `.minecraft`, if the official launcher has been used here
+    pub fn detect_official() -> Option<PathBuf> {
+        Self::get_minecraft_dir()
+            .ok()
+            .filter(|dir| dir.join(OFFICIAL_LAUNCHER_MARKER).is_file())
+    }
+
+    /// Use the `libraries/` and `assets/` of another `.minecraft` instead of this one's
+    ///
+    /// Everything else, including versions and natives, stays under `base_path`.
+    #[must_use]
+    pub fn sharing_files_from(mut self, other: &Path) -> Self {
+        self.libraries_override = Some(other.join("libraries"));
+        self.assets_override = Some(other.join("assets"));
+        self
+    }
+
+    /// Whether libraries or assets come from another directory
+    pub fn shares_files(&self) -> bool {
+        self.libraries_override.is_some() || self.assets_override.is_some()
     }
 
     /// Get the platform-specific .minecraft directory path
@@ -48,12 +94,16 @@ impl MinecraftDir {
 
     /// Get the libraries directory path
     pub fn libraries_dir(&self) -> PathBuf {
-        self.base_path.join("libraries")
+        self.libraries_override
+            .clone()
+            .unwrap_or_else(|| self.base_path.join("libraries"))
     }
 
     /// Get the assets directory path
     pub fn assets_dir(&self) -> PathBuf {
-        self.base_path.join("assets")
+        self.assets_override
+            .clone()
+            .unwrap_or_else(|| self.base_path.join("assets"))
     }
 
     /// Get the assets objects directory path
@@ -66,6 +116,21 @@ impl MinecraftDir {
         self.assets_dir().join("indexes")
     }
 
+    /// Get the launcher config file path
+    pub fn launcher_config_path(&self) -> PathBuf {
+        self.base_path.join("redstonium.json")
+    }
+
+    /// Get the path of the cached Java scan results
+    pub fn java_cache_path(&self) -> PathBuf {
+        self.base_path.join("redstonium_java_cache.json")
+    }
+
+    /// Get the instances directory path
+    pub fn instances_dir(&self) -> PathBuf {
+        self.base_path.join("instances")
+    }
+
     /// Get the path for a specific version directory
     pub fn version_dir(&self, version_id: &str) -> PathBuf {
         self.versions_dir().join(version_id)
@@ -94,11 +159,22 @@ impl MinecraftDir {
         self.assets_indexes_dir().join(format!("{asset_id}.json"))
     }
 
+    /// Get the path for a log4j configuration file
+    pub fn log_config_path(&self, config_id: &str) -> PathBuf {
+        self.assets_dir().join("log_configs").join(config_id)
+    }
+
     /// Get the path for a library
     pub fn library_path(&self, library_path: &str) -> PathBuf {
         self.libraries_dir().join(library_path)
     }
 
+    /// Get the path for a version's classpath argfile
+    pub fn classpath_argfile_path(&self, version_id: &str) -> PathBuf {
+        self.version_dir(version_id)
+            .join(format!("{version_id}-classpath.args"))
+    }
+
     /// Get the natives directory for a version
     pub fn natives_dir(&self, version_id: &str) -> PathBuf {
         self.version_dir(version_id).join("natives")
@@ -126,6 +202,42 @@ impl MinecraftDir {
         Ok(())
     }
 
+    /// IDs of all versions with a version JSON on disk, sorted
+    pub fn installed_version_ids(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(self.versions_dir()) else {
+            return Vec::new();
+        };
+
+        let mut ids: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|id| self.version_json_path(id).is_file())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Total size in bytes of all files under a directory
+    ///
+    /// Unreadable entries are skipped and symlinks are not followed. This walks the
+    /// filesystem synchronously, so call it from `spawn_blocking` in async code.
+    pub fn directory_size(path: &Path) -> u64 {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return 0;
+        };
+
+        entries
+            .flatten()
+            .map(|entry| match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => Self::directory_size(&entry.path()),
+                Ok(file_type) if file_type.is_file() => {
+                    entry.metadata().map(|m| m.len()).unwrap_or(0)
+                }
+                _ => 0,
+            })
+            .sum()
+    }
+
     /// Check if a version is installed (has both JAR and JSON files)
     pub fn is_version_installed(&self, version_id: &str) -> bool {
         let jar_path = self.version_jar_path(version_id);
@@ -1,36 +1,85 @@
+mod archive;
+mod config;
 mod files;
 mod game;
 mod instance;
 pub mod java;
+mod loader;
 mod minecraft_dir;
+mod pack;
+mod script;
+mod timings;
 mod version;
 
-pub use files::{FileManager, get_library_path};
-pub use instance::{InstanceConfig, InstanceManager};
+pub use config::LauncherConfig;
+pub use files::{Checksum, FileManager, get_library_path};
+pub use game::{LaunchSettings, ServerAddress};
+pub use instance::{InstanceConfig, InstanceManager, ModInfo, ModLoader};
 pub use java::JavaManager;
+pub use loader::{apply_loader, install_quilt, loader_versions, remove_profile};
 pub use minecraft_dir::MinecraftDir;
-pub use version::VersionType;
+pub use pack::read_pack;
+pub use timings::Timings;
+pub use version::{
+    VersionEntry, VersionInfo, VersionManifest, VersionType, is_musl, normalize_arch,
+    set_target_arch,
+};
+
+/// Launcher name reported to the game unless overridden in the launcher config
+pub const LAUNCHER_BRAND: &str = "Redstonium";
+/// Launcher version reported to the game
+pub const LAUNCHER_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 use crate::error::Result;
 use crate::{auth::AuthResult, launcher};
+use std::collections::HashSet;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell};
+
+/// Run-wide switches set by global command-line flags
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LauncherOptions {
+    /// Ignore cached version JSON and Java scan results (but keep them)
+    pub no_cache: bool,
+    /// Collect phase durations for [`Timings::print_summary`]
+    pub timings: bool,
+    /// Pick the asset download batch size from measured throughput
+    pub auto_tune: bool,
+}
 
 pub struct Launcher {
     pub minecraft_dir: MinecraftDir,
     pub file_manager: FileManager,
-    pub java_manager: JavaManager,
+    /// Scanned on first use, so commands that never run Java don't pay for it
+    java_manager: OnceCell<JavaManager>,
+    pub config: LauncherConfig,
+    /// Shared instance state. Only hold this lock for in-memory changes and
+    /// local config writes; never across network requests.
     pub instance_manager: Arc<Mutex<InstanceManager>>,
+    /// Bypass on-disk caches for this run
+    no_cache: bool,
+    /// Phase durations, printed at the end of the run with `--timings`
+    pub timings: Arc<Timings>,
 }
 
 impl Launcher {
-    pub async fn new() -> Result<Self> {
+    pub async fn new(options: LauncherOptions) -> Result<Self> {
         let minecraft_dir = MinecraftDir::new()?;
-        let file_manager = FileManager::new();
-        let mut java_manager = JavaManager::new();
-
-        // Initialize Java manager
-        java_manager.initialize();
+        let config = LauncherConfig::load(&minecraft_dir);
+        config.apply_target_arch();
+        let timings = Arc::new(Timings::new(options.timings));
+        let mut file_manager = FileManager::builder()
+            .asset_batch_size(config.asset_batch_size)
+            .auto_tune(options.auto_tune)
+            .use_cache(!options.no_cache)
+            .timings(Arc::clone(&timings));
+        if let Some(url) = &config.manifest_url {
+            file_manager = file_manager.manifest_url(url);
+        }
+        if let Some(url) = &config.resources_url {
+            file_manager = file_manager.resources_url(url);
+        }
+        let file_manager = file_manager.build();
 
         // Initialize instance manager with Arc<Mutex<>> for shared mutable access
         let instance_manager = Arc::new(Mutex::new(
@@ -40,31 +89,67 @@ impl Launcher {
         Ok(Self {
             minecraft_dir,
             file_manager,
-            java_manager,
+            java_manager: OnceCell::new(),
+            config,
             instance_manager,
+            no_cache: options.no_cache,
+            timings,
         })
     }
 
-    pub async fn prepare_game(&self, version_id: &str, _auth: &AuthResult) -> Result<()> {
-        // Download version manifest and get version info
-        let version_info = self.file_manager.get_version_info(version_id).await?;
+    /// Java installations, scanned (or read from the scan cache) on first use
+    pub async fn java_manager(&self) -> &JavaManager {
+        self.java_manager
+            .get_or_init(|| async {
+                let mut java_manager = JavaManager::new();
+                java_manager
+                    .initialize(&self.minecraft_dir.java_cache_path(), !self.no_cache)
+                    .await;
+                java_manager
+            })
+            .await
+    }
 
+    pub async fn prepare_game(&self, version_info: &VersionInfo) -> Result<()> {
         // Ensure version directory exists
-        self.minecraft_dir.ensure_version_dir(version_id)?;
+        self.minecraft_dir.ensure_version_dir(&version_info.id)?;
 
         // Download main game JAR
-        self.file_manager
-            .download_game_jar(&version_info, &self.minecraft_dir)
+        self.timings
+            .time(
+                "client jar",
+                self.file_manager
+                    .download_game_jar(version_info, &self.minecraft_dir),
+            )
             .await?;
 
         // Download libraries
-        self.file_manager
-            .download_libraries(&version_info, &self.minecraft_dir)
+        self.timings
+            .time(
+                "libraries",
+                self.file_manager.download_libraries(
+                    version_info,
+                    &self.minecraft_dir,
+                    &HashSet::new(),
+                ),
+            )
             .await?;
 
         // Download assets
+        self.timings
+            .time(
+                "assets",
+                self.file_manager.download_assets(
+                    version_info,
+                    &self.minecraft_dir,
+                    &HashSet::new(),
+                ),
+            )
+            .await?;
+
+        // Download the log4j configuration
         self.file_manager
-            .download_assets(&version_info, &self.minecraft_dir)
+            .download_logging_config(version_info, &self.minecraft_dir)
             .await?;
 
         Ok(())
@@ -72,18 +157,28 @@ impl Launcher {
 
     pub async fn launch_game(
         &self,
-        version_id: &str,
+        version_info: &VersionInfo,
         auth: &AuthResult,
         instance: Option<&InstanceConfig>,
-    ) -> Result<()> {
-        let version_info = self.file_manager.get_version_info(version_id).await?;
-        launcher::game::GameLauncher::launch(
-            &version_info,
+        settings: &LaunchSettings,
+    ) -> Result<i32> {
+        let java_manager = self
+            .timings
+            .time("java selection", self.java_manager())
+            .await;
+        let launch = launcher::game::GameLauncher::launch(
+            version_info,
             auth,
             &self.minecraft_dir,
-            &self.java_manager,
+            java_manager,
+            &self.config,
             instance,
-        )
+            settings,
+        );
+        if !settings.starts_game() {
+            return launch.await;
+        }
+        self.timings.time("game process", launch).await
     }
 }
 
@@ -116,7 +211,7 @@ mod tests {
     async fn test_launcher_initialization() {
         // Test that launcher can be initialized without panicking
         // Note: This test may fail in CI environments without proper setup
-        let result = Launcher::new().await;
+        let result = Launcher::new(LauncherOptions::default()).await;
 
         // In a test environment, we expect this might fail due to missing directories
         // but we test that it doesn't panic
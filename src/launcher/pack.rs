@@ -0,0 +1,182 @@
+use crate::error::{InstanceError, Result, ResultExt};
+use crate::launcher::archive;
+use crate::launcher::instance::ModLoader;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Index file at the root of a Modrinth `.mrpack`
+const MODRINTH_INDEX: &str = "modrinth.index.json";
+/// Manifest file at the root of a CurseForge pack zip
+const CURSEFORGE_MANIFEST: &str = "manifest.json";
+
+/// What an instance needs to know about a modpack to be created for it
+#[derive(Debug, Clone)]
+pub struct PackInfo {
+    /// Pack name, if the manifest gives one
+    pub name: Option<String>,
+    /// Minecraft version the pack targets
+    pub minecraft_version: String,
+    /// Mod loader the pack requires
+    pub loader: ModLoader,
+    /// Required loader version, if any
+    pub loader_version: Option<String>,
+}
+
+/// `modrinth.index.json`
+#[derive(Debug, Deserialize)]
+struct ModrinthIndex {
+    name: Option<String>,
+    dependencies: HashMap<String, String>,
+}
+
+/// CurseForge `manifest.json`
+#[derive(Debug, Deserialize)]
+struct CurseForgeManifest {
+    name: Option<String>,
+    minecraft: CurseForgeMinecraft,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeMinecraft {
+    version: String,
+    #[serde(default)]
+    mod_loaders: Vec<CurseForgeLoader>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeLoader {
+    id: String,
+    #[serde(default)]
+    primary: bool,
+}
+
+/// Read a Modrinth or CurseForge pack, either the zip itself or its bare manifest
+pub fn read_pack(path: &Path) -> Result<PackInfo> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read pack manifest: {}", path.display()))?;
+        return parse_pack_manifest(&content);
+    }
+
+    for name in [MODRINTH_INDEX, CURSEFORGE_MANIFEST] {
+        if let Some(content) = archive::read_archive_file(path, name)? {
+            return parse_pack_manifest(&content);
+        }
+    }
+
+    Err(InstanceError::invalid_config(format!(
+        "{} is not a Modrinth or CurseForge pack (no {MODRINTH_INDEX} or {CURSEFORGE_MANIFEST})",
+        path.display()
+    ))
+    .into())
+}
+
+/// Parse a Modrinth index or CurseForge manifest, detected by its shape
+pub fn parse_pack_manifest(content: &str) -> Result<PackInfo> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).context("Failed to parse pack manifest JSON")?;
+
+    if value.get("dependencies").is_some() {
+        let index: ModrinthIndex =
+            serde_json::from_value(value).context("Failed to parse Modrinth pack index")?;
+        return modrinth_pack_info(index);
+    }
+
+    let manifest: CurseForgeManifest =
+        serde_json::from_value(value).context("Failed to parse CurseForge pack manifest")?;
+    curseforge_pack_info(manifest)
+}
+
+fn modrinth_pack_info(index: ModrinthIndex) -> Result<PackInfo> {
+    let Some(minecraft_version) = index.dependencies.get("minecraft").cloned() else {
+        return Err(InstanceError::invalid_config(
+            "Modrinth pack does not declare a Minecraft version",
+        )
+        .into());
+    };
+
+    let (loader, loader_version) = if let Some(version) = index.dependencies.get("forge") {
+        (ModLoader::Forge, Some(version.clone()))
+    } else if let Some(version) = index.dependencies.get("fabric-loader") {
+        (ModLoader::Fabric, Some(version.clone()))
+    } else if let Some(version) = index.dependencies.get("quilt-loader") {
+        (ModLoader::Quilt, Some(version.clone()))
+    } else if index.dependencies.contains_key("neoforge") {
+        return Err(unsupported_loader("neoforge"));
+    } else {
+        (ModLoader::Vanilla, None)
+    };
+
+    Ok(PackInfo {
+        name: index.name,
+        minecraft_version,
+        loader,
+        loader_version,
+    })
+}
+
+fn curseforge_pack_info(manifest: CurseForgeManifest) -> Result<PackInfo> {
+    let loaders = &manifest.minecraft.mod_loaders;
+    let primary = loaders
+        .iter()
+        .find(|loader| loader.primary)
+        .or_else(|| loaders.first());
+
+    // Loader IDs look like `forge-47.2.0` or `fabric-0.15.3`
+    let (loader, loader_version) = match primary {
+        None => (ModLoader::Vanilla, None),
+        Some(entry) => {
+            let (kind, version) = entry.id.split_once('-').unwrap_or((&entry.id, ""));
+            let loader = match kind {
+                "forge" => ModLoader::Forge,
+                "fabric" => ModLoader::Fabric,
+                "quilt" => ModLoader::Quilt,
+                other => return Err(unsupported_loader(other)),
+            };
+            (loader, (!version.is_empty()).then(|| version.to_string()))
+        }
+    };
+
+    Ok(PackInfo {
+        name: manifest.name,
+        minecraft_version: manifest.minecraft.version,
+        loader,
+        loader_version,
+    })
+}
+
+fn unsupported_loader(loader: &str) -> crate::error::RustifiedError {
+    InstanceError::invalid_config(format!("Mod loader '{loader}' is not supported")).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_modrinth_index() {
+        let info = parse_pack_manifest(
+            r#"{"formatVersion":1,"game":"minecraft","name":"Pack","files":[],
+                "dependencies":{"minecraft":"1.20.1","fabric-loader":"0.15.3"}}"#,
+        )
+        .unwrap();
+        assert_eq!(info.name.as_deref(), Some("Pack"));
+        assert_eq!(info.minecraft_version, "1.20.1");
+        assert!(matches!(info.loader, ModLoader::Fabric));
+        assert_eq!(info.loader_version.as_deref(), Some("0.15.3"));
+    }
+
+    #[test]
+    fn test_parse_curseforge_manifest() {
+        let info = parse_pack_manifest(
+            r#"{"manifestType":"minecraftModpack","name":"CF Pack","files":[],
+                "minecraft":{"version":"1.19.2","modLoaders":[{"id":"forge-43.3.0","primary":true}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(info.minecraft_version, "1.19.2");
+        assert!(matches!(info.loader, ModLoader::Forge));
+        assert_eq!(info.loader_version.as_deref(), Some("43.3.0"));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::error::{Result, ResultExt};
+use std::path::Path;
+use std::process::Command;
+use tracing::warn;
+
+/// Environment variable a launch script reads the access token from
+pub const ACCESS_TOKEN_VAR: &str = "MINECRAFT_ACCESS_TOKEN";
+
+/// Longest command line cmd.exe will run
+const BATCH_LINE_LIMIT: usize = 8191;
+
+/// Language of a launch script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptKind {
+    Shell,
+    Batch,
+    PowerShell,
+}
+
+impl ScriptKind {
+    /// Pick the language from the file extension, defaulting to the platform's own
+    fn for_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("ps1") => Self::PowerShell,
+            Some("bat" | "cmd") => Self::Batch,
+            Some("sh") => Self::Shell,
+            _ if cfg!(windows) => Self::Batch,
+            _ => Self::Shell,
+        }
+    }
+
+    /// Quote a literal so the script passes it through unchanged
+    fn quote(self, value: &str) -> String {
+        match self {
+            Self::Shell => format!("'{}'", value.replace('\'', "'\\''")),
+            Self::Batch => format!("\"{}\"", value.replace('%', "%%").replace('"', "\\\"")),
+            Self::PowerShell => format!("'{}'", value.replace('\'', "''")),
+        }
+    }
+
+    /// Quote an argument, reading any occurrence of the access token from the environment
+    fn argument(self, arg: &str, access_token: &str) -> String {
+        if access_token.is_empty() || !arg.contains(access_token) {
+            return self.quote(arg);
+        }
+
+        let parts = arg.split(access_token);
+        let quoted = |part: &str| {
+            if part.is_empty() {
+                String::new()
+            } else {
+                self.quote(part)
+            }
+        };
+        match self {
+            Self::Shell => parts
+                .map(quoted)
+                .collect::<Vec<_>>()
+                .join(&format!("\"${ACCESS_TOKEN_VAR}\"")),
+            Self::Batch => parts
+                .map(quoted)
+                .collect::<Vec<_>>()
+                .join(&format!("%{ACCESS_TOKEN_VAR}%")),
+            // Variables only expand inside double quotes, where ` escapes
+            Self::PowerShell => {
+                let escaped: Vec<String> = parts
+                    .map(|part| {
+                        part.replace('`', "``")
+                            .replace('$', "`$")
+                            .replace('"', "`\"")
+                    })
+                    .collect();
+                format!(
+                    "\"{}\"",
+                    escaped.join(&format!("${{env:{ACCESS_TOKEN_VAR}}}"))
+                )
+            }
+        }
+    }
+}
+
+/// Write a script that runs `cmd` outside the launcher
+///
+/// The access token is session-specific, so the script reads it from
+/// `MINECRAFT_ACCESS_TOKEN` and asks for it when unset. `.ps1` files get PowerShell,
+/// `.bat`/`.cmd` batch and `.sh` a POSIX shell script; otherwise the platform's own.
+pub fn write_launch_script(cmd: &Command, access_token: &str, path: &Path) -> Result<()> {
+    let kind = ScriptKind::for_path(path);
+    let script = render_script(kind, cmd, access_token);
+    std::fs::write(path, script)
+        .with_context(|| format!("Failed to write launch script to {}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", path.display()))?;
+    }
+    Ok(())
+}
+
+fn render_script(kind: ScriptKind, cmd: &Command, access_token: &str) -> String {
+    let newline = if kind == ScriptKind::Shell {
+        "\n"
+    } else {
+        "\r\n"
+    };
+    let arguments: Vec<String> = std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|arg| kind.argument(&arg.to_string_lossy(), access_token))
+        .collect();
+    let needs_token = !access_token.is_empty()
+        && cmd
+            .get_args()
+            .any(|arg| arg.to_string_lossy().contains(access_token));
+    let env: Vec<(String, String)> = cmd
+        .get_envs()
+        .filter_map(|(key, value)| {
+            Some((
+                key.to_string_lossy().into_owned(),
+                value?.to_string_lossy().into_owned(),
+            ))
+        })
+        .collect();
+    let dir = cmd
+        .get_current_dir()
+        .map(|dir| kind.quote(&dir.to_string_lossy()));
+
+    let mut lines = Vec::new();
+    match kind {
+        ScriptKind::Shell => {
+            lines.push("#!/bin/sh".to_string());
+            lines.push("# Minecraft launch script generated by Redstonium".to_string());
+            if needs_token {
+                lines.push(format!("if [ -z \"${ACCESS_TOKEN_VAR}\" ]; then"));
+                lines.push("    printf 'Access token: ' >&2".to_string());
+                lines.push(format!("    read -r {ACCESS_TOKEN_VAR}"));
+                lines.push("fi".to_string());
+            }
+            for (key, value) in &env {
+                lines.push(format!("export {key}={}", kind.quote(value)));
+            }
+            if let Some(dir) = dir {
+                lines.push(format!("cd {dir} || exit 1"));
+            }
+            lines.push(format!("exec {}", arguments.join(" \\\n    ")));
+        }
+        ScriptKind::Batch => {
+            lines.push("@echo off".to_string());
+            lines.push("rem Minecraft launch script generated by Redstonium".to_string());
+            if needs_token {
+                lines.push(format!(
+                    "if not defined {ACCESS_TOKEN_VAR} set /p {ACCESS_TOKEN_VAR}=Access token: "
+                ));
+            }
+            for (key, value) in &env {
+                lines.push(format!("set \"{key}={}\"", value.replace('%', "%%")));
+            }
+            if let Some(dir) = dir {
+                lines.push(format!("cd /d {dir} || exit /b 1"));
+            }
+            let command_line = arguments.join(" ");
+            if command_line.len() > BATCH_LINE_LIMIT {
+                warn!(
+                    "⚠ Launch command is {} characters, longer than cmd.exe allows; use a .ps1 script instead",
+                    command_line.len()
+                );
+            }
+            lines.push(command_line);
+            lines.push("exit /b %ERRORLEVEL%".to_string());
+        }
+        ScriptKind::PowerShell => {
+            lines.push("# Minecraft launch script generated by Redstonium".to_string());
+            if needs_token {
+                lines.push(format!(
+                    "if (-not $env:{ACCESS_TOKEN_VAR}) {{ $env:{ACCESS_TOKEN_VAR} = Read-Host 'Access token' }}"
+                ));
+            }
+            for (key, value) in &env {
+                lines.push(format!("$env:{key} = {}", kind.quote(value)));
+            }
+            if let Some(dir) = dir {
+                lines.push(format!("Set-Location -LiteralPath {dir}"));
+            }
+            lines.push(format!("& {}", arguments.join(&format!(" `{newline}    "))));
+            lines.push("exit $LASTEXITCODE".to_string());
+        }
+    }
+
+    let mut script = lines.join(newline);
+    script.push_str(newline);
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_command() -> Command {
+        let mut cmd = Command::new("java");
+        cmd.args(["-Xmx2G", "--accessToken", "secret", "--username", "it's me"]);
+        cmd
+    }
+
+    #[test]
+    fn test_shell_script_reads_token_from_environment() {
+        let script = render_script(ScriptKind::Shell, &test_command(), "secret");
+        assert!(!script.contains("secret"));
+        assert!(script.contains("read -r MINECRAFT_ACCESS_TOKEN"));
+        assert!(script.contains("'--accessToken' \\\n    \"$MINECRAFT_ACCESS_TOKEN\" \\"));
+        assert!(script.contains("'it'\\''s me'"));
+    }
+
+    #[test]
+    fn test_powershell_and_batch_scripts() {
+        let powershell = render_script(ScriptKind::PowerShell, &test_command(), "secret");
+        assert!(powershell.contains("\"${env:MINECRAFT_ACCESS_TOKEN}\""));
+        assert!(powershell.contains("'it''s me'"));
+
+        let batch = render_script(ScriptKind::Batch, &test_command(), "secret");
+        assert!(batch.contains("\"--accessToken\" %MINECRAFT_ACCESS_TOKEN% "));
+        assert!(!batch.contains("secret"));
+    }
+
+    #[test]
+    fn test_script_kind_for_path() {
+        assert_eq!(
+            ScriptKind::for_path(Path::new("play.ps1")),
+            ScriptKind::PowerShell
+        );
+        assert_eq!(
+            ScriptKind::for_path(Path::new("play.BAT")),
+            ScriptKind::Batch
+        );
+        assert_eq!(
+            ScriptKind::for_path(Path::new("play.sh")),
+            ScriptKind::Shell
+        );
+    }
+}
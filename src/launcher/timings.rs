@@ -0,0 +1,55 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Wall-clock durations of the launcher's major phases, collected for `--timings`
+#[derive(Debug, Default)]
+pub struct Timings {
+    enabled: bool,
+    phases: Mutex<Vec<(&'static str, Duration)>>,
+}
+
+impl Timings {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            phases: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Await `future`, recording how long it took under `phase`
+    pub async fn time<T>(&self, phase: &'static str, future: impl Future<Output = T>) -> T {
+        let started = Instant::now();
+        let output = future.await;
+        self.record(phase, started.elapsed());
+        output
+    }
+
+    /// Record a phase's duration; does nothing unless timings were requested
+    pub fn record(&self, phase: &'static str, duration: Duration) {
+        if !self.enabled {
+            return;
+        }
+        if let Ok(mut phases) = self.phases.lock() {
+            phases.push((phase, duration));
+        }
+    }
+
+    /// Print the recorded phases in the order they finished, with their total
+    pub fn print_summary(&self) {
+        let Ok(phases) = self.phases.lock() else {
+            return;
+        };
+        if phases.is_empty() {
+            return;
+        }
+
+        info!("Timings:");
+        for (phase, duration) in phases.iter() {
+            info!("  {phase:<20} {:>10}", format!("{duration:.2?}"));
+        }
+        let total: Duration = phases.iter().map(|(_, duration)| *duration).sum();
+        info!("  {:<20} {:>10}", "total", format!("{total:.2?}"));
+    }
+}
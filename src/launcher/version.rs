@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::OnceLock;
+use tracing::debug;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct VersionManifest {
@@ -22,6 +24,66 @@ pub struct VersionEntry {
     pub time: String,
     #[serde(rename = "releaseTime")]
     pub release_time: String,
+    /// SHA1 of the version JSON, listed by the v2 manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+}
+
+impl VersionEntry {
+    /// SHA1 of the version JSON, from the v2 manifest or the package URL it is served from
+    pub fn json_sha1(&self) -> Option<&str> {
+        self.sha1.as_deref().or_else(|| {
+            let mut segments = self.url.rsplit('/').skip(1);
+            segments
+                .next()
+                .filter(|hash| hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        })
+    }
+
+    /// Parse the RFC3339 release time, returning `None` if it is malformed
+    pub fn release_date(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::parse_from_rfc3339(&self.release_time)
+            .ok()
+            .map(|date| date.with_timezone(&chrono::Utc))
+    }
+
+    /// Whether this is an experimental, combat-test or April Fools build
+    ///
+    /// The manifest lists these as ordinary snapshots, so they are recognised by ID.
+    pub fn is_experimental(&self) -> bool {
+        const APRIL_FOOLS: [&str; 8] = [
+            "15w14a",
+            "1.RV-Pre1",
+            "3D Shareware v1.34",
+            "20w14infinite",
+            "22w13oneblockatatime",
+            "23w13a_or_b",
+            "24w14potato",
+            "25w14craftmine",
+        ];
+
+        let id = self.id.to_lowercase();
+        id.contains("experimental")
+            || id.contains("_combat-")
+            || id.contains("-exp")
+            || APRIL_FOOLS.contains(&self.id.as_str())
+    }
+
+    /// The `major.minor.patch` of a release-style ID like `1.20.6` or `1.20.5-pre1`
+    ///
+    /// A missing patch counts as 0. Snapshots like `24w14a` and old alphas and betas
+    /// have no release number.
+    pub fn release_number(&self) -> Option<(u32, u32, u32)> {
+        let numbers = self.id.split(['-', ' ']).next()?;
+        let mut parts = numbers.split('.').map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some((major, minor, patch))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -55,6 +117,62 @@ pub struct VersionInfo {
     pub assets: String,
     #[serde(rename = "javaVersion")]
     pub java_version: Option<JavaVersion>,
+    #[serde(default)]
+    pub logging: Option<Logging>,
+}
+
+impl VersionInfo {
+    /// Library and native downloads that apply to this platform, with their Maven names
+    pub fn library_downloads(&self) -> impl Iterator<Item = (String, &DownloadInfo)> {
+        self.libraries
+            .iter()
+            .filter(|library| library.should_use())
+            .flat_map(|library| {
+                let artifact = library
+                    .downloads
+                    .artifact
+                    .as_ref()
+                    .map(|download| (library.name.clone(), download));
+                let native = library.get_native_classifier().and_then(|classifier| {
+                    let download = library.downloads.classifiers.as_ref()?.get(&classifier)?;
+                    Some((format!("{}:{classifier}", library.name), download))
+                });
+                artifact.into_iter().chain(native)
+            })
+    }
+
+    /// Approximate bytes to download for this version on this platform: the client
+    /// JAR, the libraries that apply and every asset
+    pub fn download_size(&self) -> u64 {
+        let libraries: u64 = self
+            .library_downloads()
+            .map(|(_, download)| download.size)
+            .sum();
+        self.downloads.client.size + libraries + self.asset_index.total_size
+    }
+}
+
+/// Logging configuration shipped with a version (log4j config file and JVM argument)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Logging {
+    pub client: Option<LoggingConfig>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LoggingConfig {
+    /// JVM argument template, e.g. `-Dlog4j.configurationFile=${path}`
+    pub argument: String,
+    pub file: LoggingFile,
+    #[serde(rename = "type")]
+    pub config_type: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LoggingFile {
+    pub id: String,
+    pub sha1: String,
+    pub size: u64,
+    pub url: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -93,6 +211,9 @@ pub struct LibraryDownloads {
 pub struct Rule {
     pub action: String,
     pub os: Option<OsRule>,
+    /// Launcher features the rule requires (e.g. `is_demo_user`)
+    #[serde(default)]
+    pub features: Option<HashMap<String, bool>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -102,6 +223,124 @@ pub struct OsRule {
     pub version: Option<String>,
 }
 
+impl OsRule {
+    /// Check the rule's `arch` against the target architecture
+    pub fn arch_matches(&self) -> bool {
+        let Some(arch) = &self.arch else {
+            return true;
+        };
+        let current_arch = match target_arch() {
+            "aarch64" => "arm64",
+            other => other,
+        };
+        arch == current_arch
+    }
+
+    /// Check the rule's `version` regex against an OS version string
+    ///
+    /// Rules without a version pattern match any OS version.
+    pub fn version_matches(&self, os_version: Option<&str>) -> bool {
+        let Some(pattern) = &self.version else {
+            return true;
+        };
+        let Some(os_version) = os_version else {
+            return false;
+        };
+
+        match regex::Regex::new(pattern) {
+            Ok(regex) => regex.is_match(os_version),
+            Err(e) => {
+                debug!("Ignoring invalid OS version pattern '{pattern}': {e}");
+                false
+            }
+        }
+    }
+}
+
+/// Architecture used for native selection and rule evaluation, when overridden
+static TARGET_ARCH: OnceLock<&'static str> = OnceLock::new();
+
+/// Normalise an architecture name to the form `std::env::consts::ARCH` uses
+pub fn normalize_arch(arch: &str) -> Option<&'static str> {
+    match arch.trim().to_ascii_lowercase().as_str() {
+        "x86_64" | "amd64" | "x64" => Some("x86_64"),
+        "aarch64" | "arm64" => Some("aarch64"),
+        "x86" | "i386" | "i686" => Some("x86"),
+        _ => None,
+    }
+}
+
+/// Override the target architecture (e.g. to run x86_64 natives under Rosetta)
+///
+/// The first override wins, so a command-line flag takes precedence over the config file.
+pub fn set_target_arch(arch: &'static str) {
+    if TARGET_ARCH.set(arch).is_ok() && arch != std::env::consts::ARCH {
+        debug!("Targeting {arch} instead of {}", std::env::consts::ARCH);
+    }
+}
+
+/// The architecture override, if one was set
+pub fn target_arch_override() -> Option<&'static str> {
+    TARGET_ARCH.get().copied()
+}
+
+/// The architecture the game is launched for
+pub fn target_arch() -> &'static str {
+    target_arch_override().unwrap_or(std::env::consts::ARCH)
+}
+
+/// Whether the system C library is musl (e.g. Alpine Linux) rather than glibc
+///
+/// Detected once by looking for the musl dynamic loader in `/lib`.
+pub fn is_musl() -> bool {
+    static IS_MUSL: OnceLock<bool> = OnceLock::new();
+    *IS_MUSL.get_or_init(|| {
+        if std::env::consts::OS != "linux" {
+            return false;
+        }
+        cfg!(target_env = "musl")
+            || std::fs::read_dir("/lib").is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| entry.file_name().to_string_lossy().starts_with("ld-musl-"))
+            })
+    })
+}
+
+/// The current OS version in the form Java reports as `os.version`
+///
+/// Detected once: `sw_vers` on macOS, `ver` on Windows, and `uname -r` elsewhere.
+pub fn current_os_version() -> Option<&'static str> {
+    static OS_VERSION: OnceLock<Option<String>> = OnceLock::new();
+    OS_VERSION.get_or_init(detect_os_version).as_deref()
+}
+
+fn detect_os_version() -> Option<String> {
+    let run = |program: &str, args: &[&str]| {
+        let output = std::process::Command::new(program)
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let version = match std::env::consts::OS {
+        "macos" => run("sw_vers", &["-productVersion"])?,
+        "windows" => {
+            // "Microsoft Windows [Version 10.0.19045.3693]" -> "10.0"
+            let output = run("cmd", &["/C", "ver"])?;
+            let full = output.rsplit(' ').next()?.trim_end_matches(']');
+            full.split('.').take(2).collect::<Vec<_>>().join(".")
+        }
+        _ => run("uname", &["-r"])?,
+    };
+
+    (!version.is_empty()).then_some(version)
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ExtractRules {
     pub exclude: Option<Vec<String>>,
@@ -193,20 +432,8 @@ impl Library {
                 }
             }
 
-            // Check architecture
-            if let Some(arch) = &os_rule.arch {
-                let current_arch = match std::env::consts::ARCH {
-                    "x86_64" => "x86_64",
-                    "aarch64" => "arm64",
-                    _ => return false,
-                };
-
-                if arch != current_arch {
-                    return false;
-                }
-            }
-
-            true
+            // Check architecture and OS version
+            os_rule.arch_matches() && os_rule.version_matches(current_os_version())
         } else {
             true // No OS rule means it matches all platforms
         }
@@ -221,7 +448,11 @@ impl Library {
                 _ => return None,
             };
 
-            natives.get(os_name).cloned()
+            // Legacy classifiers such as `natives-windows-${arch}` expect the pointer width
+            let bits = if target_arch() == "x86" { "32" } else { "64" };
+            natives
+                .get(os_name)
+                .map(|classifier| classifier.replace("${arch}", bits))
         } else {
             None
         }
@@ -231,4 +462,133 @@ impl Library {
     pub fn is_native_library(&self) -> bool {
         self.name.contains(":natives-")
     }
+
+    /// Maven coordinates without the version (`group:artifact[:classifier]`)
+    ///
+    /// Two entries with the same key are the same library at different versions.
+    pub fn maven_key(&self) -> String {
+        let mut parts = self.name.split(':');
+        let group = parts.next().unwrap_or_default();
+        let artifact = parts.next().unwrap_or_default();
+        match parts.nth(1) {
+            Some(classifier) => format!("{group}:{artifact}:{classifier}"),
+            None => format!("{group}:{artifact}"),
+        }
+    }
+
+    /// The version part of the Maven coordinates
+    pub fn maven_version(&self) -> &str {
+        self.name.split(':').nth(2).unwrap_or_default()
+    }
+}
+
+/// Compare two Maven-style version strings segment by segment
+///
+/// Numeric segments compare numerically (`3.10` > `3.9`); anything else compares as text.
+pub fn compare_library_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let split =
+        |v: &str| -> Vec<String> { v.split(['.', '-', '_']).map(ToString::to_string).collect() };
+    let (a, b) = (split(a), split(b));
+
+    for (left, right) in a.iter().zip(&b) {
+        let ordering = match (left.parse::<u64>(), right.parse::<u64>()) {
+            (Ok(l), Ok(r)) => l.cmp(&r),
+            _ => left.cmp(right),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn os_rule(version: Option<&str>) -> OsRule {
+        OsRule {
+            name: Some("osx".to_string()),
+            arch: None,
+            version: version.map(ToString::to_string),
+        }
+    }
+
+    fn entry(id: &str, version_type: VersionType) -> VersionEntry {
+        VersionEntry {
+            id: id.to_string(),
+            version_type,
+            url: String::new(),
+            time: String::new(),
+            release_time: String::new(),
+            sha1: None,
+        }
+    }
+
+    #[test]
+    fn test_os_rule_version_matching() {
+        let rule = os_rule(Some(r"^10\.5\.\d$"));
+        assert!(rule.version_matches(Some("10.5.8")));
+        assert!(!rule.version_matches(Some("14.2.1")));
+        assert!(!rule.version_matches(None));
+
+        let any_version = os_rule(None);
+        assert!(any_version.version_matches(Some("14.2.1")));
+        assert!(any_version.version_matches(None));
+
+        assert!(!os_rule(Some("(unclosed")).version_matches(Some("10.5.8")));
+    }
+
+    #[test]
+    fn test_is_experimental() {
+        let snapshot = |id| entry(id, VersionType::Snapshot);
+        assert!(snapshot("1.18_experimental-snapshot-1").is_experimental());
+        assert!(snapshot("1.14_combat-212796").is_experimental());
+        assert!(snapshot("24w14potato").is_experimental());
+        assert!(!snapshot("24w14a").is_experimental());
+        assert!(!snapshot("1.21-pre1").is_experimental());
+    }
+
+    #[test]
+    fn test_release_number() {
+        let release = |id| entry(id, VersionType::Release);
+        assert_eq!(release("1.20.6").release_number(), Some((1, 20, 6)));
+        assert_eq!(release("1.21").release_number(), Some((1, 21, 0)));
+        assert_eq!(release("1.20.5-pre1").release_number(), Some((1, 20, 5)));
+        assert_eq!(
+            release("1.14.4 Pre-Release 1").release_number(),
+            Some((1, 14, 4))
+        );
+        assert_eq!(release("24w14a").release_number(), None);
+        assert_eq!(release("b1.7.3").release_number(), None);
+        assert_eq!(release("1.RV-Pre1").release_number(), None);
+    }
+
+    #[test]
+    fn test_json_sha1() {
+        let from_json = |extra: serde_json::Value| {
+            let mut json = serde_json::json!({
+                "id": "1.20.1",
+                "type": "release",
+                "url": "https://piston-meta.mojang.com/v1/packages/715ccf3330885e75b205124f09f8712542cbe7e0/1.20.1.json",
+                "time": "2023-06-12T13:25:51+00:00",
+                "releaseTime": "2023-06-12T13:25:51+00:00"
+            });
+            json.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<VersionEntry>(json).unwrap()
+        };
+
+        assert_eq!(
+            from_json(serde_json::json!({})).json_sha1(),
+            Some("715ccf3330885e75b205124f09f8712542cbe7e0")
+        );
+        assert_eq!(
+            from_json(serde_json::json!({"sha1": "00ff"})).json_sha1(),
+            Some("00ff")
+        );
+        let mirror = from_json(serde_json::json!({"url": "https://mirror.example/1.20.1.json"}));
+        assert_eq!(mirror.json_sha1(), None);
+    }
 }
@@ -3,6 +3,7 @@
 //! This module provides a beautifully formatted logger with colored output,
 //! custom time formatting, and structured logging capabilities.
 
+use crate::cli::LogFormat;
 use std::fmt;
 use tracing_subscriber::fmt::FmtContext;
 use tracing_subscriber::fmt::format::Writer;
@@ -84,30 +85,45 @@ where
 /// Initialize the logging system with custom formatting
 ///
 /// This function sets up a beautiful, colored logger with custom time formatting
-/// and consistent level alignment. It respects the `RUST_LOG` environment variable
-/// for filtering, falling back to "info" level.
+/// and consistent level alignment, or a JSON logger emitting one object per event
+/// for log aggregators. It respects the `RUST_LOG` environment variable
+/// for filtering, falling back to "info" level. Logs go to stderr, so stdout only
+/// carries command output like `manifest --json` that other tools read.
 ///
 /// # Examples
 ///
 /// ```rust
 /// // Initialize the logger
-/// Redstonium::logger::init();
+/// Redstonium::logger::init(LogFormat::Text);
 /// ```
-pub fn init() {
+pub fn init(format: LogFormat) {
     let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
 
-    tracing_subscriber::fmt()
-        .with_timer(CustomTimeFormat)
-        .with_target(false)
-        .with_thread_ids(false)
-        .with_thread_names(false)
-        .with_file(false)
-        .with_line_number(false)
-        .with_level(true)
-        .with_ansi(true)
-        .with_env_filter(env_filter)
-        .fmt_fields(tracing_subscriber::fmt::format::DefaultFields::new())
-        .event_format(CustomFormat)
-        .init();
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt()
+            .with_timer(CustomTimeFormat)
+            .with_target(false)
+            .with_thread_ids(false)
+            .with_thread_names(false)
+            .with_file(false)
+            .with_line_number(false)
+            .with_level(true)
+            .with_ansi(true)
+            .with_env_filter(env_filter)
+            .with_writer(std::io::stderr)
+            .fmt_fields(tracing_subscriber::fmt::format::DefaultFields::new())
+            .event_format(CustomFormat)
+            .init(),
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_span_list(false)
+            .with_target(false)
+            .with_ansi(false)
+            .with_env_filter(env_filter)
+            .with_writer(std::io::stderr)
+            .init(),
+    }
 }
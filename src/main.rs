@@ -11,17 +11,32 @@ pub mod error;
 mod launcher;
 mod logger;
 
-use crate::cli::{Cli, Commands};
+use crate::cli::{Cli, Commands, VersionCommands};
 use clap::Parser;
 use tracing::{debug, error, info};
 
 #[tokio::main]
 async fn main() -> crate::error::Result<()> {
-    // Initialize the logger with custom format
-    logger::init();
-
     let cli = Cli::parse();
 
+    // Initialize the logger with the requested format
+    logger::init(cli.log_format);
+
+    // Completion output goes to stdout and must not include the banner
+    match cli.command {
+        Commands::Completions { shell } => {
+            commands::completions::print_completions(shell);
+            return Ok(());
+        }
+        Commands::CompleteInstances => return commands::completions::print_instance_names(),
+        _ => {}
+    }
+
+    // A command-line override takes precedence over the launcher config
+    if let Some(arch) = cli.target_arch {
+        launcher::set_target_arch(arch);
+    }
+
     info!(
         "Redstonium Minecraft Launcher v{}",
         env!("CARGO_PKG_VERSION")
@@ -30,7 +45,12 @@ async fn main() -> crate::error::Result<()> {
 
     // Initialize the launcher
     debug!("Initializing launcher...");
-    let launcher = match launcher::Launcher::new().await {
+    let options = launcher::LauncherOptions {
+        no_cache: cli.no_cache,
+        timings: cli.timings,
+        auto_tune: cli.auto_tune,
+    };
+    let launcher = match launcher::Launcher::new(options).await {
         Ok(launcher) => {
             debug!("✓ Launcher initialized successfully");
             launcher
@@ -41,7 +61,14 @@ async fn main() -> crate::error::Result<()> {
         }
     };
 
-    match cli.command {
+    let result = run_command(&launcher, cli.command).await;
+    launcher.timings.print_summary();
+    result
+}
+
+/// Dispatches a command that needs an initialized launcher
+async fn run_command(launcher: &launcher::Launcher, command: Commands) -> crate::error::Result<()> {
+    match command {
         Commands::List {
             types,
             releases_only,
@@ -50,6 +77,13 @@ async fn main() -> crate::error::Result<()> {
             filter,
             show_installed,
             sort,
+            since,
+            until,
+            count,
+            exclude_experimental,
+            show_java,
+            fetch_java,
+            latest_per_minor,
         } => {
             let options = commands::game::ListVersionsOptions {
                 types,
@@ -59,26 +93,59 @@ async fn main() -> crate::error::Result<()> {
                 filter,
                 show_installed,
                 sort,
+                since,
+                until,
+                count,
+                exclude_experimental,
+                show_java,
+                fetch_java,
+                latest_per_minor,
             };
-            commands::game::list_versions(&launcher, options).await?;
+            commands::game::list_versions(launcher, options).await?;
         }
-        Commands::Launch {
-            instance,
-            skip_verification,
-        } => {
-            commands::game::launch_game(&launcher, &instance, skip_verification).await?;
+        Commands::Launch { instance, options } => {
+            let exit_code = commands::game::launch_game(launcher, &instance, &options).await?;
+            if exit_code != 0 {
+                launcher.timings.print_summary();
+                // Let scripts see the game's own exit status
+                std::process::exit(exit_code);
+            }
         }
-        Commands::Prepare { version } => {
-            commands::game::prepare_game(&launcher, &version).await?;
+        Commands::Prepare { options } => {
+            commands::game::run_prepare(launcher, options).await?;
         }
         Commands::Auth { action } => {
-            commands::auth::handle_auth_command(action).await?;
+            let exit_code = commands::auth::handle_auth_command(action).await?;
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
         }
         Commands::Instance { action } => {
-            commands::instance::handle_instance_command(&launcher, action).await?;
+            commands::instance::handle_instance_command(launcher, action).await?;
         }
         Commands::Java { action } => {
-            commands::java::handle_java_command(&launcher, action);
+            commands::java::handle_java_command(launcher, action).await?;
+        }
+        Commands::Versions => {
+            commands::versions::list_installed_versions(launcher).await?;
+        }
+        Commands::Version {
+            action: VersionCommands::Info { id },
+        } => commands::versions::show_version_info(launcher, &id).await?,
+        Commands::Search { query, limit } => {
+            commands::game::search_versions(launcher, &query, limit).await?;
+        }
+        Commands::Manifest { json } => {
+            commands::game::show_manifest(launcher, json).await?;
+        }
+        Commands::Disk => {
+            commands::disk::show_disk_usage(launcher).await?;
+        }
+        Commands::Doctor => {
+            commands::doctor::run_doctor(launcher).await;
+        }
+        Commands::Completions { .. } | Commands::CompleteInstances => {
+            // Handled before the launcher is initialized
         }
     }
 